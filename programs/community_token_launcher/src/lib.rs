@@ -1,14 +1,42 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
 declare_id!("8MHXGF2A4np7ipWHMNe9msonHZNeKFuBvPDZdQXBnv8q");
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 // Constants
 pub const MAX_CHOICES: usize = 10;
+pub const ABSTAIN_CHOICE: &str = "Abstain";
+pub const MAX_TITLE_LENGTH: usize = 100;
+pub const MAX_DESCRIPTION_LENGTH: usize = 500;
+pub const MAX_CHOICE_LENGTH: usize = 50;
+pub const MAX_TOKEN_NAME_LENGTH: usize = 32;
+pub const MAX_TOKEN_SYMBOL_LENGTH: usize = 8;
+pub const MIN_VOTING_DURATION: i64 = 60; // 1 minute
+pub const TIE_EXTENSION_PERIOD: i64 = 86_400; // 1 day
+// Escrows per distribution batch, kept low enough to fit the default compute budget and
+// a legacy transaction's account keys (three accounts per escrow, 31 keys at the cap)
+pub const MAX_BATCH_ESCROWS: usize = 8;
+// Escrows per refund batch, which needs four accounts per escrow (30 keys at the cap)
+pub const MAX_REFUND_BATCH_ESCROWS: usize = 6;
+// Largest leftover vault balance (in base units) that can be swept after settlement
+pub const MAX_ESCROW_DUST: u64 = 10;
+// Token mints listed per registry index page; a full page rolls over to the next one
+pub const REGISTRY_INDEX_PAGE_SIZE: usize = 64;
 
 #[program]
 pub mod community_token_launcher {
     use super::*;
 
+    pub fn initialize_registry_index(ctx: Context<InitializeRegistryIndex>) -> Result<()> {
+        let registry_index = &mut ctx.accounts.registry_index;
+        registry_index.current_page = 0;
+        registry_index.total_mints = 0;
+
+        msg!("Registry index initialized");
+
+        Ok(())
+    }
+
     pub fn initialize_token_registry(
         ctx: Context<InitializeTokenRegistry>,
         token_name: String,
@@ -24,6 +52,25 @@ pub mod community_token_launcher {
         token_registry.launch_timestamp = Clock::get()?.unix_timestamp;
         token_registry.governance_enabled = false;
         token_registry.is_initialized = true;
+        token_registry.pending_authority = None;
+
+        // List the mint so clients can enumerate registered tokens
+        let registry_index = &mut ctx.accounts.registry_index;
+        let registry_index_page = &mut ctx.accounts.registry_index_page;
+        registry_index_page.page = registry_index.current_page;
+        registry_index_page.mints.push(ctx.accounts.token_mint.key());
+        registry_index.total_mints = registry_index
+            .total_mints
+            .checked_add(1)
+            .ok_or(ErrorCode::CalculationError)?;
+
+        // The next registration opens a fresh page once this one is full
+        if registry_index_page.mints.len() >= REGISTRY_INDEX_PAGE_SIZE {
+            registry_index.current_page = registry_index
+                .current_page
+                .checked_add(1)
+                .ok_or(ErrorCode::CalculationError)?;
+        }
         
         msg!("Token Registry initialized for {}", token_name);
         
@@ -68,10 +115,7 @@ pub mod community_token_launcher {
             id: choice_id,
             name: proposal.choices[choice_id as usize].clone(),
             vote_count: proposal.choice_vote_counts[choice_id as usize],
-            is_winning: match proposal.winning_choice {
-                Some(winning_id) => winning_id == choice_id,
-                None => false,
-            },
+            is_winning: proposal.is_winning_choice(choice_id),
         };
         
         msg!("Retrieved choice data: {} (ID: {})", choice_data.name, choice_id);
@@ -96,6 +140,13 @@ pub mod community_token_launcher {
             created_at: proposal.created_at,
             ends_at: proposal.ends_at,
             winning_choice: proposal.winning_choice,
+            winner_recipient_override: proposal.winner_recipient_override,
+            abstain_choice: proposal.abstain_choice,
+            start_at: proposal.start_at,
+            winners_count: proposal.winners_count,
+            winning_choices: proposal.winning_choices.clone(),
+            vetoed_by: proposal.vetoed_by,
+            category: proposal.category,
         };
         
         msg!("Retrieved proposal data for: {} (ID: {})", proposal.title, proposal_id);
@@ -104,14 +155,221 @@ pub mod community_token_launcher {
         Ok(proposal_data)
     }
 
+    pub fn get_proposal_results(ctx: Context<GetProposal>, proposal_id: u64) -> Result<ProposalResults> {
+        let proposal = &ctx.accounts.proposal;
+
+        let proposal_results = ProposalResults {
+            id: proposal.id,
+            status: proposal.status.clone(),
+            winning_choice: proposal.winning_choice,
+            winning_choices: proposal.winning_choices.clone(),
+            total_votes: proposal.total_votes(),
+            choice_vote_counts: proposal.choice_vote_counts.clone(),
+            category: proposal.category,
+        };
+
+        msg!("Retrieved results for proposal {}", proposal_id);
+
+        Ok(proposal_results)
+    }
+
+    pub fn get_execution_status(
+        ctx: Context<GetProposal>,
+        proposal_id: u64,
+    ) -> Result<ExecutionStatus> {
+        let governance = &ctx.accounts.governance;
+        let proposal = &ctx.accounts.proposal;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let already_executed = proposal.status != ProposalStatus::Active;
+        let voting_ended = current_time > proposal.ends_at;
+        let threshold_met = proposal.total_votes() >= governance.min_vote_threshold;
+        let quorum_met = proposal.meets_quorum(governance.quorum_percentage);
+
+        // Report the first check execute_proposal would fail on, in the same order
+        let blocking_reason = if !voting_ended {
+            Some(ExecutionBlocker::VotingNotEnded)
+        } else if already_executed {
+            Some(ExecutionBlocker::ProposalNotActive)
+        } else if !threshold_met {
+            Some(ExecutionBlocker::VoteThresholdNotMet)
+        } else if !quorum_met {
+            Some(ExecutionBlocker::QuorumNotMet)
+        } else {
+            None
+        };
+
+        let execution_status = ExecutionStatus {
+            voting_ended,
+            threshold_met,
+            quorum_met,
+            already_executed,
+            executable: blocking_reason.is_none(),
+            blocking_reason,
+        };
+
+        msg!("Retrieved execution status for proposal {}", proposal_id);
+
+        Ok(execution_status)
+    }
+
+    pub fn get_proposal_phase(
+        ctx: Context<GetProposal>,
+        proposal_id: u64,
+    ) -> Result<ProposalPhase> {
+        let proposal = &ctx.accounts.proposal;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        // A final status takes precedence over the voting window
+        let phase = match proposal.status {
+            ProposalStatus::Executed => VotingPhase::Executed,
+            ProposalStatus::Rejected => VotingPhase::Rejected,
+            ProposalStatus::Active if current_time < proposal.start_at => VotingPhase::Pending,
+            ProposalStatus::Active if current_time <= proposal.ends_at => VotingPhase::Voting,
+            ProposalStatus::Active => VotingPhase::Ended,
+        };
+
+        let proposal_phase = ProposalPhase {
+            phase,
+            seconds_until_start: proposal.start_at.saturating_sub(current_time).max(0),
+            seconds_until_end: proposal.ends_at.saturating_sub(current_time).max(0),
+        };
+
+        msg!("Retrieved phase for proposal {}", proposal_id);
+
+        Ok(proposal_phase)
+    }
+
+    pub fn is_fully_settled(ctx: Context<GetProposal>, proposal_id: u64) -> Result<bool> {
+        let proposal = &ctx.accounts.proposal;
+
+        // Settled once the outcome is final and every escrow has been paid out
+        let is_final = proposal.status == ProposalStatus::Executed
+            || proposal.status == ProposalStatus::Rejected;
+        let settled = is_final && proposal.open_escrow_count == 0;
+
+        msg!("Proposal {} fully settled: {}", proposal_id, settled);
+
+        Ok(settled)
+    }
+
+    pub fn update_token_info(
+        ctx: Context<UpdateTokenInfo>,
+        token_name: Option<String>,
+        token_symbol: Option<String>,
+    ) -> Result<()> {
+        let token_registry = &mut ctx.accounts.token_registry;
+
+        if let Some(token_name) = token_name {
+            require!(token_name.len() <= MAX_TOKEN_NAME_LENGTH, ErrorCode::NameTooLong);
+            token_registry.token_name = token_name;
+        }
+        if let Some(token_symbol) = token_symbol {
+            require!(token_symbol.len() <= MAX_TOKEN_SYMBOL_LENGTH, ErrorCode::SymbolTooLong);
+            token_registry.token_symbol = token_symbol;
+        }
+
+        msg!("Token info updated: {} ({})", token_registry.token_name, token_registry.token_symbol);
+
+        Ok(())
+    }
+
+    pub fn propose_registry_transfer(
+        ctx: Context<ProposeRegistryTransfer>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let token_registry = &mut ctx.accounts.token_registry;
+        token_registry.pending_authority = Some(new_authority);
+
+        msg!("Registry authority transfer proposed to {}", new_authority);
+
+        Ok(())
+    }
+
+    pub fn accept_registry_transfer(ctx: Context<AcceptRegistryTransfer>) -> Result<()> {
+        let new_authority = ctx.accounts.new_authority.key();
+
+        let token_registry = &mut ctx.accounts.token_registry;
+        token_registry.authority = new_authority;
+        token_registry.pending_authority = None;
+
+        // Governance control must follow the registry, so its account is required once
+        // governance exists
+        match ctx.accounts.governance.as_mut() {
+            Some(governance) => governance.authority = new_authority,
+            None => require!(
+                !token_registry.governance_enabled,
+                ErrorCode::GovernanceAccountRequired
+            ),
+        }
+
+        msg!("Registry authority transferred to {}", new_authority);
+
+        Ok(())
+    }
+
+    pub fn get_token_registry(ctx: Context<GetTokenRegistry>) -> Result<RegistryInfo> {
+        let token_registry = &ctx.accounts.token_registry;
+
+        let registry_info = RegistryInfo {
+            authority: token_registry.authority,
+            token_mint: token_registry.token_mint,
+            token_name: token_registry.token_name.clone(),
+            token_symbol: token_registry.token_symbol.clone(),
+            launch_timestamp: token_registry.launch_timestamp,
+            governance_enabled: token_registry.governance_enabled,
+            is_initialized: token_registry.is_initialized,
+            pending_authority: token_registry.pending_authority,
+        };
+
+        msg!("Retrieved token registry for {}", token_registry.token_name);
+
+        Ok(registry_info)
+    }
+
+    pub fn check_token_holder(ctx: Context<CheckTokenHolder>) -> Result<bool> {
+        let token_account = &ctx.accounts.token_account;
+        let is_holder = token_account.amount > 0;
+
+        msg!("{} holds {} tokens", token_account.owner, token_account.amount);
+
+        Ok(is_holder)
+    }
+
     pub fn initialize_governance(
         ctx: Context<InitializeGovernance>,
         voting_period: i64,
         min_vote_threshold: u64,
         proposal_threshold: u64,
         proposal_threshold_percentage: u8,
+        quorum_percentage: u8,
+        max_lifetime_proposals: u64,
+        tie_break: TieBreak,
+        execution_timelock: i64,
+        per_choice_threshold: u64,
+        min_hold_duration: i64,
+        max_active_proposals: u64,
+        min_vote_amount: u64,
+        max_voting_period: i64,
         name: String,
     ) -> Result<()> {
+        // Percentage-based thresholds can't be evaluated against a token with no supply
+        require!(
+            proposal_threshold_percentage == 0 || ctx.accounts.token_mint.supply > 0,
+            ErrorCode::InvalidGovernanceSettings
+        );
+        require!(quorum_percentage <= 100, ErrorCode::InvalidGovernanceSettings);
+        require!(
+            voting_period >= MIN_VOTING_DURATION,
+            ErrorCode::InvalidGovernanceSettings
+        );
+        require!(execution_timelock >= 0, ErrorCode::InvalidGovernanceSettings);
+        require!(min_hold_duration >= 0, ErrorCode::InvalidGovernanceSettings);
+        require!(
+            max_voting_period == 0 || max_voting_period >= voting_period,
+            ErrorCode::InvalidGovernanceSettings
+        );
+
         // Initialize governance data
         let governance = &mut ctx.accounts.governance;
         governance.authority = ctx.accounts.authority.key();
@@ -122,9 +380,20 @@ pub mod community_token_launcher {
         governance.min_vote_threshold = min_vote_threshold;
         governance.proposal_threshold = proposal_threshold;
         governance.proposal_threshold_percentage = proposal_threshold_percentage;
+        governance.quorum_percentage = quorum_percentage;
+        governance.max_lifetime_proposals = max_lifetime_proposals;
+        governance.tie_break = tie_break;
+        governance.execution_timelock = execution_timelock;
+        governance.per_choice_threshold = per_choice_threshold;
+        governance.min_hold_duration = min_hold_duration;
+        governance.active_proposal_count = 0;
+        governance.max_active_proposals = max_active_proposals;
+        governance.min_vote_amount = min_vote_amount;
+        governance.max_voting_period = max_voting_period;
         governance.name = name.clone();
         governance.is_active = true;
         governance.created_at = Clock::get()?.unix_timestamp;
+        governance.bump = ctx.bumps.governance;
         
         // Update token registry to show governance is enabled
         let token_registry = &mut ctx.accounts.token_registry;
@@ -135,35 +404,259 @@ pub mod community_token_launcher {
         Ok(())
     }
 
+    pub fn set_governance_active(ctx: Context<SetGovernanceActive>, active: bool) -> Result<()> {
+        let governance = &mut ctx.accounts.governance;
+        governance.is_active = active;
+
+        msg!("Governance {} active: {}", governance.name, active);
+
+        Ok(())
+    }
+
     pub fn lock_tokens_for_choice(
         ctx: Context<LockTokensForChoice>,
         amount: u64,
         choice_id: u8,
     ) -> Result<()> {
-        // SPL transfer from voter → choice escrow vault
-        token::transfer(
-            CpiContext::new(
+        require!(
+            amount >= ctx.accounts.governance.min_vote_amount,
+            ErrorCode::VoteAmountTooSmall
+        );
+
+        let voter = ctx.accounts.voter.key();
+        cast_vote(
+            &ctx.accounts.token_program,
+            &mut ctx.accounts.proposal,
+            &mut ctx.accounts.voter_receipt,
+            &mut ctx.accounts.choice_escrow,
+            &ctx.accounts.voter_token_account,
+            &ctx.accounts.choice_escrow_vault,
+            ctx.accounts.voter.to_account_info(),
+            voter,
+            choice_id,
+            amount,
+        )?;
+        ctx.accounts.choice_escrow.bump = ctx.bumps.choice_escrow;
+
+        msg!("User voted with {} tokens", amount);
+
+        Ok(())
+    }
+
+    pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Pubkey) -> Result<()> {
+        let delegator = ctx.accounts.delegator.key();
+        require!(
+            delegate != delegator && delegate != Pubkey::default(),
+            ErrorCode::InvalidDelegate
+        );
+
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.delegator = delegator;
+        delegation.token_mint = ctx.accounts.token_mint.key();
+        delegation.delegate = delegate;
+
+        msg!("Voting power of {} delegated to {}", delegator, delegate);
+
+        Ok(())
+    }
+
+    pub fn clear_delegate(ctx: Context<ClearDelegate>) -> Result<()> {
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.delegate = Pubkey::default();
+
+        msg!("Delegation cleared for {}", delegation.delegator);
+
+        Ok(())
+    }
+
+    /// Votes on behalf of a delegator. The delegator's tokens are moved, so they must
+    /// also have approved the delegate on their token account for at least `amount`.
+    pub fn lock_tokens_for_choice_as_delegate(
+        ctx: Context<LockTokensForChoiceAsDelegate>,
+        amount: u64,
+        choice_id: u8,
+    ) -> Result<()> {
+        require!(
+            amount >= ctx.accounts.governance.min_vote_amount,
+            ErrorCode::VoteAmountTooSmall
+        );
+
+        // The escrow is recorded under the delegator so settlement routes back to them
+        let delegator = ctx.accounts.delegator.key();
+        cast_vote(
+            &ctx.accounts.token_program,
+            &mut ctx.accounts.proposal,
+            &mut ctx.accounts.voter_receipt,
+            &mut ctx.accounts.choice_escrow,
+            &ctx.accounts.voter_token_account,
+            &ctx.accounts.choice_escrow_vault,
+            ctx.accounts.delegate.to_account_info(),
+            delegator,
+            choice_id,
+            amount,
+        )?;
+        ctx.accounts.choice_escrow.bump = ctx.bumps.choice_escrow;
+
+        msg!("Delegate {} voted with {} tokens for {}",
+            ctx.accounts.delegate.key(), amount, delegator);
+
+        Ok(())
+    }
+
+    pub fn reduce_vote(ctx: Context<ReduceVote>, amount: u64) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let escrow = &mut ctx.accounts.choice_escrow;
+
+        // Votes can only be reduced while voting is still open
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(current_time <= proposal.ends_at, ErrorCode::VotingEnded);
+        require!(amount > 0, ErrorCode::ZeroReduction);
+        require!(
+            amount <= escrow.locked_amount,
+            ErrorCode::ReductionExceedsLockedAmount
+        );
+        // A partial reduction cannot leave a dust vote below the governance minimum
+        let remaining = escrow.locked_amount - amount;
+        require!(
+            remaining == 0 || remaining >= ctx.accounts.governance.min_vote_amount,
+            ErrorCode::VoteAmountTooSmall
+        );
+
+        // Update the escrow and tally before returning the tokens
+        escrow.locked_amount = remaining;
+        escrow.credited_power = escrow.credited_power.saturating_sub(amount);
+        proposal.decrement_vote_count(escrow.choice_id, amount)?;
+        // Only the reduction that empties a funded escrow closes it out
+        if escrow.locked_amount == 0 {
+            proposal.open_escrow_count = proposal
+                .open_escrow_count
+                .checked_sub(1)
+                .ok_or(ErrorCode::CalculationError)?;
+        }
+
+        // Transfer the tokens back to the voter
+        safe_transfer(
+            &ctx.accounts.token_program,
+            &ctx.accounts.escrow_vault,
+            &ctx.accounts.voter_token_account,
+            ctx.accounts.vault_authority.to_account_info(),
+            &[&[
+                b"vault_authority",
+                proposal.key().as_ref(),
+                &[escrow.choice_id],
+                escrow.voter.as_ref(),
+                &[ctx.bumps.vault_authority]
+            ]],
+            amount,
+        )?;
+
+        msg!("User reduced vote by {} tokens", amount);
+
+        Ok(())
+    }
+
+    /// Moves a voter's locked tokens to another choice. Escrow and vault addresses are
+    /// derived from the choice, so the balance is transferred into a vault for the new
+    /// choice and the old escrow and vault are closed back to the voter.
+    pub fn change_vote(ctx: Context<ChangeVote>, new_choice_id: u8) -> Result<()> {
+        let proposal_key = ctx.accounts.proposal.key();
+        let old_choice_id = ctx.accounts.old_choice_escrow.choice_id;
+        let amount = ctx.accounts.old_choice_escrow.locked_amount;
+
+        // Votes can only be changed while voting is open
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            current_time >= ctx.accounts.proposal.start_at,
+            ErrorCode::VotingNotStarted
+        );
+        require!(current_time <= ctx.accounts.proposal.ends_at, ErrorCode::VotingEnded);
+        require!(new_choice_id != old_choice_id, ErrorCode::ChoiceUnchanged);
+        require!(amount > 0, ErrorCode::EmptyEscrow);
+
+        let voter = ctx.accounts.voter.key();
+        let authority_seeds: &[&[&[u8]]] = &[&[
+            b"vault_authority",
+            proposal_key.as_ref(),
+            &[old_choice_id],
+            voter.as_ref(),
+            &[ctx.bumps.old_vault_authority]
+        ]];
+
+        let surplus = ctx
+            .accounts
+            .old_escrow_vault
+            .amount
+            .checked_sub(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+
+        // Move the locked tokens into the vault for the new choice
+        safe_transfer(
+            &ctx.accounts.token_program,
+            &ctx.accounts.old_escrow_vault,
+            &ctx.accounts.new_escrow_vault,
+            ctx.accounts.old_vault_authority.to_account_info(),
+            authority_seeds,
+            amount,
+        )?;
+
+        // Tokens sent to the vault directly were never counted as votes; return them so
+        // the vault can be closed
+        if surplus > 0 {
+            safe_transfer(
+                &ctx.accounts.token_program,
+                &ctx.accounts.old_escrow_vault,
+                &ctx.accounts.voter_token_account,
+                ctx.accounts.old_vault_authority.to_account_info(),
+                authority_seeds,
+                surplus,
+            )?;
+        }
+
+        // Close the emptied vault; the old escrow is closed to the voter by Anchor
+        token::close_account(
+            CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from:      ctx.accounts.voter_token_account.to_account_info(),
-                    to:        ctx.accounts.choice_escrow_vault.to_account_info(),
-                    authority: ctx.accounts.voter.to_account_info(),
+                token::CloseAccount {
+                    account: ctx.accounts.old_escrow_vault.to_account_info(),
+                    destination: ctx.accounts.voter.to_account_info(),
+                    authority: ctx.accounts.old_vault_authority.to_account_info(),
                 },
+                authority_seeds,
             ),
-            amount,
         )?;
 
-        let escrow = &mut ctx.accounts.choice_escrow;
-        escrow.voter = ctx.accounts.voter.key();
-        escrow.proposal = ctx.accounts.proposal.key();
-        escrow.choice_id = choice_id;
-        escrow.locked_amount = amount;
+        let new_escrow = &mut ctx.accounts.new_choice_escrow;
+        new_escrow.voter = voter;
+        new_escrow.proposal = proposal_key;
+        new_escrow.choice_id = new_choice_id;
+        new_escrow.locked_amount = amount;
+        new_escrow.credited_power = amount;
+        new_escrow.voted_at = current_time;
+        new_escrow.bump = ctx.bumps.new_choice_escrow;
 
-        // Update proposal vote counts for this choice
+        // Shift the tally; the open escrow count is unchanged as one escrow replaces another
         let proposal = &mut ctx.accounts.proposal;
-        proposal.update_vote_count(choice_id, amount)?;
+        proposal.decrement_vote_count(old_choice_id, amount)?;
+        proposal.update_vote_count(new_choice_id, amount)?;
 
-        msg!("User voted with {} tokens", amount);
+        ctx.accounts.voter_receipt.choice_id = new_choice_id;
+
+        msg!("User moved {} tokens from choice {} to choice {}",
+            amount, old_choice_id, new_choice_id);
+
+        Ok(())
+    }
+
+    /// Records the holder's current balance, which proposal creation can require to be
+    /// at least `min_hold_duration` old. Recording again resets the snapshot's age.
+    pub fn record_holding(ctx: Context<RecordHolding>) -> Result<()> {
+        let snapshot = &mut ctx.accounts.holder_snapshot;
+        snapshot.holder = ctx.accounts.holder.key();
+        snapshot.token_mint = ctx.accounts.token_mint.key();
+        snapshot.amount = ctx.accounts.holder_token_account.amount;
+        snapshot.recorded_at = Clock::get()?.unix_timestamp;
+
+        msg!("Recorded holding of {} tokens for {}", snapshot.amount, snapshot.holder);
 
         Ok(())
     }
@@ -174,19 +667,112 @@ pub mod community_token_launcher {
         description: String,
         choices: Vec<String>,
         voting_duration: Option<i64>,
+        winner_recipient_override: Option<Pubkey>,
+        has_abstain: bool,
+        start_at: Option<i64>,
+        winners_count: u8,
+        category: u8,
     ) -> Result<()> {
         let proposal = &mut ctx.accounts.proposal;
         let proposer = &ctx.accounts.proposer;
 
-        // Validate choices
+        // Validate choices, leaving room for the reserved abstain choice if requested
         require!(choices.len() > 1, ErrorCode::InvalidChoicesCount);
-        require!(choices.len() <= MAX_CHOICES, ErrorCode::TooManyChoices);
+        require!(
+            choices.len() + has_abstain as usize <= MAX_CHOICES,
+            ErrorCode::TooManyChoices
+        );
+
+        require!(
+            winners_count >= 1 && winners_count as usize <= choices.len(),
+            ErrorCode::InvalidWinnersCount
+        );
+
+        // String lengths must stay within the space reserved for them
+        require!(title.len() <= MAX_TITLE_LENGTH, ErrorCode::TitleTooLong);
+        require!(description.len() <= MAX_DESCRIPTION_LENGTH, ErrorCode::DescriptionTooLong);
+        require!(
+            choices.iter().all(|choice| choice.len() <= MAX_CHOICE_LENGTH),
+            ErrorCode::ChoiceTooLong
+        );
+
+        // Every choice needs a distinct, non-blank label
+        for (i, choice) in choices.iter().enumerate() {
+            let label = choice.trim();
+            require!(!label.is_empty(), ErrorCode::EmptyChoice);
+            require!(
+                !choices[..i].iter().any(|other| other.trim() == label)
+                    && !(has_abstain && label == ABSTAIN_CHOICE),
+                ErrorCode::DuplicateChoice
+            );
+        }
+
+        // The proposer must hold both the absolute and the percentage-based threshold
+        let governance = &ctx.accounts.governance;
+        let mut proposer_balance = ctx.accounts.proposer_token_account.amount;
+
+        // Only count tokens held since an aged snapshot, so a same-transaction loan can't qualify
+        if governance.min_hold_duration > 0 {
+            let snapshot = ctx
+                .accounts
+                .holder_snapshot
+                .as_ref()
+                .ok_or(ErrorCode::HoldingSnapshotRequired)?;
+            let held_since = snapshot
+                .recorded_at
+                .checked_add(governance.min_hold_duration)
+                .ok_or(ErrorCode::CalculationError)?;
+            require!(
+                Clock::get()?.unix_timestamp >= held_since,
+                ErrorCode::HoldingTooRecent
+            );
+            proposer_balance = proposer_balance.min(snapshot.amount);
+        }
+
+        require!(
+            proposer_balance >= governance.proposal_threshold,
+            ErrorCode::InsufficientTokensToPropose
+        );
+
+        if governance.proposal_threshold_percentage > 0 {
+            // u128 intermediates so high-supply tokens can't overflow
+            let total_supply = ctx.accounts.token_mint.supply as u128;
+            require!(total_supply > 0, ErrorCode::InvalidGovernanceSettings);
+
+            let required_balance = total_supply
+                .checked_mul(governance.proposal_threshold_percentage as u128)
+                .and_then(|value| value.checked_div(100))
+                .ok_or(ErrorCode::CalculationError)?;
+            require!(
+                proposer_balance as u128 >= required_balance,
+                ErrorCode::InsufficientTokensToPropose
+            );
+
+            let holding_percentage = (proposer_balance as u128)
+                .checked_mul(100)
+                .and_then(|value| value.checked_div(total_supply))
+                .ok_or(ErrorCode::CalculationError)?;
+            msg!("Proposer holds {}% of the token supply", holding_percentage);
+        }
+
+        // Respect the optional cap on proposals over the governance's lifetime
+        require!(
+            governance.max_lifetime_proposals == 0
+                || governance.proposal_count < governance.max_lifetime_proposals,
+            ErrorCode::ProposalLimitReached
+        );
+        require!(
+            governance.max_active_proposals == 0
+                || governance.active_proposal_count < governance.max_active_proposals,
+            ErrorCode::TooManyActiveProposals
+        );
 
         // Get proposal ID from governance
         let proposal_id = ctx.accounts.governance.proposal_count;
 
         // Update governance proposal count directly
         ctx.accounts.governance.proposal_count += 1;
+        ctx.accounts.governance.active_proposal_count += 1;
 
         // Initialize the proposal
         proposal.id = proposal_id;
@@ -195,24 +781,57 @@ pub mod community_token_launcher {
         proposal.token_creator = ctx.accounts.token_registry.authority;
         proposal.title = title.clone();
         proposal.description = description;
+        let mut choices = choices;
+        proposal.abstain_choice = None;
+        if has_abstain {
+            proposal.abstain_choice = Some(choices.len() as u8);
+            choices.push(ABSTAIN_CHOICE.to_string());
+        }
         let choices_len = choices.len();
         proposal.choices = choices;
         proposal.choice_vote_counts = vec![0; choices_len];
         proposal.status = ProposalStatus::Active;
         proposal.created_at = Clock::get()?.unix_timestamp;
+        proposal.supply_snapshot = ctx.accounts.token_mint.supply;
+
+        // Voting opens immediately unless a future start time is given
+        let start_at = start_at.unwrap_or(proposal.created_at);
+        require!(start_at >= proposal.created_at, ErrorCode::InvalidStartTime);
+        proposal.start_at = start_at;
         
         // Use custom voting duration if provided and valid, otherwise use the governance default
         let duration = match voting_duration {
             Some(duration) => {
                 // Require minimum of 60 seconds (1 minute)
-                require!(duration >= 60, ErrorCode::VotingDurationTooShort);
-                duration
+                require!(duration >= MIN_VOTING_DURATION, ErrorCode::VotingDurationTooShort);
+                // Overrides longer than the governance ceiling are clamped to it (0 = no ceiling)
+                match ctx.accounts.governance.max_voting_period {
+                    0 => duration,
+                    max_voting_period => duration.min(max_voting_period),
+                }
             },
             None => ctx.accounts.governance.voting_period,
         };
         
-        proposal.ends_at = proposal.created_at + duration;
+        proposal.ends_at = proposal
+            .start_at
+            .checked_add(duration)
+            .ok_or(ErrorCode::CalculationError)?;
         proposal.winning_choice = None;
+        proposal.winners_count = winners_count;
+        proposal.winning_choices = Vec::new();
+        proposal.winner_recipient_override = winner_recipient_override;
+        proposal.vetoed_by = None;
+        proposal.category = category;
+        proposal.executed_at = 0;
+
+        // Make sure the serialized proposal fits the space allocated for it
+        let mut serialized = Vec::new();
+        (**proposal).try_serialize(&mut serialized)?;
+        require!(
+            serialized.len() <= proposal.to_account_info().data_len(),
+            ErrorCode::InputTooLong
+        );
 
         msg!("Multi-choice proposal created: {} (ID: {})", title, proposal_id);
 
@@ -223,6 +842,13 @@ pub mod community_token_launcher {
         let proposal = &mut ctx.accounts.proposal;
         let token_registry = &ctx.accounts.token_registry;
 
+        // A proposal without choices (or with mismatched tallies) cannot have a winner
+        require!(
+            !proposal.choices.is_empty()
+                && proposal.choice_vote_counts.len() == proposal.choices.len(),
+            ErrorCode::CorruptProposalState
+        );
+
         // Explicitly verify that the executor is the token registry authority
         require!(
             ctx.accounts.executor.key() == token_registry.authority,
@@ -236,74 +862,126 @@ pub mod community_token_launcher {
         // Check if proposal is still active status
         require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
 
-        // Find the winning choice
-        let mut max_votes = 0;
-        let mut winning_index = 0;
+        // Participation requirements, including abstain votes
+        let governance = &mut ctx.accounts.governance;
+        require!(
+            proposal.total_votes() >= governance.min_vote_threshold,
+            ErrorCode::VoteThresholdNotMet
+        );
+        require!(
+            proposal.meets_quorum(governance.quorum_percentage),
+            ErrorCode::QuorumNotMet
+        );
+
+        // Rank the choices by votes; the stable sort keeps the lowest index first among
+        // equal counts. Abstain votes count as participation but can never win.
+        let counts = &proposal.choice_vote_counts;
+        let mut ranking: Vec<usize> = (0..counts.len())
+            .filter(|&i| proposal.abstain_choice != Some(i as u8))
+            .collect();
+        ranking.sort_by(|&a, &b| counts[b].cmp(&counts[a]));
+        require!(!ranking.is_empty(), ErrorCode::NoWinningChoice);
+
+        // The last winning slot is tied when the best losing choice has the same count
+        let winners_count = (proposal.winners_count as usize).clamp(1, ranking.len());
+        let cutoff_votes = counts[ranking[winners_count - 1]];
+        let is_tie = ranking
+            .get(winners_count)
+            .is_some_and(|&i| counts[i] == cutoff_votes);
+
+        // Every winner needs meaningful support of its own, not just overall turnout
+        if cutoff_votes < governance.per_choice_threshold {
+            proposal.status = ProposalStatus::Rejected;
+            proposal.winning_choice = None;
+            governance.release_active_proposal()?;
+            msg!("Winning choice has {} votes, below the per-choice threshold of {}: proposal rejected",
+                cutoff_votes, governance.per_choice_threshold);
+            return Ok(());
+        }
 
-        for (i, &votes) in proposal.choice_vote_counts.iter().enumerate() {
-            if votes > max_votes {
-                max_votes = votes;
-                winning_index = i;
+        if is_tie {
+            match governance.tie_break {
+                TieBreak::LowestIndex => {
+                    msg!("Tie resolved in favour of the lowest index");
+                }
+                TieBreak::Reject => {
+                    proposal.status = ProposalStatus::Rejected;
+                    proposal.winning_choice = None;
+                    governance.release_active_proposal()?;
+                    msg!("Tie on {} votes: proposal rejected", cutoff_votes);
+                    return Ok(());
+                }
+                TieBreak::ExtendVoting => {
+                    proposal.ends_at = proposal
+                        .ends_at
+                        .checked_add(TIE_EXTENSION_PERIOD)
+                        .ok_or(ErrorCode::CalculationError)?;
+                    msg!("Tie on {} votes: voting extended until {}", cutoff_votes, proposal.ends_at);
+                    return Ok(());
+                }
             }
         }
 
-        // Set the winning choice
+        // Set the winning choices, leader first
+        let winning_index = ranking[0];
+        proposal.winning_choices = ranking[..winners_count].iter().map(|&i| i as u8).collect();
         proposal.winning_choice = Some(winning_index as u8);
         proposal.status = ProposalStatus::Executed;
+        proposal.executed_at = current_time;
+        governance.release_active_proposal()?;
 
-        msg!("Proposal executed. Winning choice: {} (index {})",
-            proposal.choices[winning_index], winning_index);
+        let winning_name = proposal
+            .choices
+            .get(winning_index)
+            .map(String::as_str)
+            .unwrap_or("<unknown>");
+        msg!("Proposal executed. Winning choice: {} (index {}), winners: {:?}",
+            winning_name, winning_index, proposal.winning_choices);
 
         Ok(())
     }
 
-    pub fn distribute_winning_escrow(ctx: Context<DistributeWinningEscrow>) -> Result<()> {
-        let proposal = &ctx.accounts.proposal;
-        let escrow = &ctx.accounts.choice_escrow;
+    /// Marks a proposal that ended without meeting the vote threshold or quorum as
+    /// Rejected, so every escrow locked on it can be refunded.
+    pub fn finalize_failed_proposal(ctx: Context<FinalizeFailedProposal>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let governance = &mut ctx.accounts.governance;
 
-        // Ensure proposal is executed and has a winning choice
-        require!(
-            proposal.status == ProposalStatus::Executed,
-            ErrorCode::ProposalNotExecuted
-        );
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(current_time > proposal.ends_at, ErrorCode::VotingNotEnded);
+        require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
 
-        let winning_choice = proposal.winning_choice.ok_or(ErrorCode::NoWinningChoice)?;
+        let failed = proposal.total_votes() < governance.min_vote_threshold
+            || !proposal.meets_quorum(governance.quorum_percentage);
+        require!(failed, ErrorCode::ProposalNotFailed);
 
-        // Verify this escrow is for the winning choice
-        require!(
-            escrow.choice_id == winning_choice,
-            ErrorCode::NotWinningEscrow
-        );
+        proposal.status = ProposalStatus::Rejected;
+        proposal.winning_choice = None;
+        governance.release_active_proposal()?;
 
-        // Transfer the tokens to token creator
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from: ctx.accounts.escrow_vault.to_account_info(),
-                    to: ctx.accounts.creator_token_account.to_account_info(),
-                    authority: ctx.accounts.vault_authority.to_account_info(),
-                },
-                &[&[
-                    b"vault_authority",
-                    proposal.key().as_ref(),
-                    &[escrow.choice_id],
-                    escrow.voter.as_ref(),
-                    &[ctx.bumps.vault_authority]
-                ]],
-            ),
-            escrow.locked_amount,
-        )?;
+        msg!("Proposal {} rejected with {} total votes", proposal.id, proposal.total_votes());
 
-        msg!("Transferred {} tokens from winning escrow to token creator",
-            escrow.locked_amount);
+        Ok(())
+    }
+
+    /// Rejects an active proposal regardless of its votes, releasing every escrow for refund.
+    pub fn veto_proposal(ctx: Context<VetoProposal>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
+
+        proposal.status = ProposalStatus::Rejected;
+        proposal.winning_choice = None;
+        proposal.vetoed_by = Some(ctx.accounts.authority.key());
+        ctx.accounts.governance.release_active_proposal()?;
+
+        msg!("Proposal {} vetoed by {}", proposal.id, ctx.accounts.authority.key());
 
         Ok(())
     }
 
-    pub fn refund_losing_escrow(ctx: Context<RefundLosingEscrow>) -> Result<()> {
-        let proposal = &ctx.accounts.proposal;
-        let escrow = &ctx.accounts.choice_escrow;
+    pub fn distribute_winning_escrow(ctx: Context<DistributeWinningEscrow>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let escrow = &mut ctx.accounts.choice_escrow;
 
         // Ensure proposal is executed and has a winning choice
         require!(
@@ -311,31 +989,204 @@ pub mod community_token_launcher {
             ErrorCode::ProposalNotExecuted
         );
 
-        let winning_choice = proposal.winning_choice.ok_or(ErrorCode::NoWinningChoice)?;
+        require!(proposal.winning_choice.is_some(), ErrorCode::NoWinningChoice);
 
-        // Verify this escrow is NOT for the winning choice
+        // Give the community time to react before winning funds move
+        let current_time = Clock::get()?.unix_timestamp;
         require!(
-            escrow.choice_id != winning_choice,
-            ErrorCode::IsWinningEscrow
+            current_time >= proposal.distribution_unlocks_at(ctx.accounts.governance.execution_timelock)?,
+            ErrorCode::TimelockActive
         );
 
-        // Transfer the tokens back to the voter
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from: ctx.accounts.escrow_vault.to_account_info(),
-                    to: ctx.accounts.voter_token_account.to_account_info(),
-                    authority: ctx.accounts.vault_authority.to_account_info(),
-                },
+        // Verify this escrow is for one of the winning choices
+        require!(
+            proposal.is_winning_choice(escrow.choice_id),
+            ErrorCode::NotWinningEscrow
+        );
+        require!(escrow.locked_amount > 0, ErrorCode::EmptyEscrow);
+        require!(!escrow.distributed, ErrorCode::AlreadyDistributed);
+
+        // Record the settlement before any tokens move
+        escrow.distributed = true;
+        proposal.open_escrow_count = proposal
+            .open_escrow_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::CalculationError)?;
+
+        // Transfer the tokens to the token creator or the proposal's override recipient
+        safe_transfer(
+            &ctx.accounts.token_program,
+            &ctx.accounts.escrow_vault,
+            &ctx.accounts.creator_token_account,
+            ctx.accounts.vault_authority.to_account_info(),
+            &[&[
+                b"vault_authority",
+                proposal.key().as_ref(),
+                &[escrow.choice_id],
+                escrow.voter.as_ref(),
+                &[ctx.bumps.vault_authority]
+            ]],
+            escrow.locked_amount,
+        )?;
+
+        msg!("Transferred {} tokens from winning escrow to {}",
+            escrow.locked_amount, proposal.winner_recipient());
+
+        Ok(())
+    }
+
+    /// Distributes several winning escrows in one call. `remaining_accounts` must be
+    /// passed as (choice_escrow, escrow_vault, vault_authority) triples, at most
+    /// MAX_BATCH_ESCROWS of them. Escrows for other choices are skipped.
+    pub fn distribute_winning_escrows_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeWinningEscrowsBatch<'info>>,
+    ) -> Result<()> {
+        let remaining_accounts = ctx.remaining_accounts;
+        require!(
+            remaining_accounts.len() % 3 == 0,
+            ErrorCode::InvalidBatchAccounts
+        );
+        require!(
+            remaining_accounts.len() / 3 <= MAX_BATCH_ESCROWS,
+            ErrorCode::BatchTooLarge
+        );
+
+        let proposal_key = ctx.accounts.proposal.key();
+        let token_mint_key = ctx.accounts.token_mint.key();
+        require!(
+            ctx.accounts.proposal.winning_choice.is_some(),
+            ErrorCode::NoWinningChoice
+        );
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let unlocks_at = ctx
+            .accounts
+            .proposal
+            .distribution_unlocks_at(ctx.accounts.governance.execution_timelock)?;
+        require!(current_time >= unlocks_at, ErrorCode::TimelockActive);
+
+        let mut distributed_count: u64 = 0;
+        let mut distributed_amount: u64 = 0;
+
+        for escrow_accounts in remaining_accounts.chunks(3) {
+            let mut escrow = Account::<ChoiceEscrow>::try_from(&escrow_accounts[0])?;
+            let escrow_vault = Account::<TokenAccount>::try_from(&escrow_accounts[1])?;
+            let vault_authority = &escrow_accounts[2];
+
+            require!(escrow.proposal == proposal_key, ErrorCode::InvalidBatchAccounts);
+
+            // Only winning escrows that still hold tokens are distributed
+            if !ctx.accounts.proposal.is_winning_choice(escrow.choice_id)
+                || escrow.locked_amount == 0
+                || escrow.distributed
+            {
+                continue;
+            }
+
+            // The vault must be this escrow's own vault PDA, not just any token account
+            // owned by its authority
+            let (expected_authority, authority_bump) = Pubkey::find_program_address(
+                &[
+                    b"vault_authority",
+                    proposal_key.as_ref(),
+                    &[escrow.choice_id],
+                    escrow.voter.as_ref(),
+                ],
+                ctx.program_id,
+            );
+            let (expected_vault, _) = Pubkey::find_program_address(
+                &[
+                    b"choice_escrow_vault",
+                    proposal_key.as_ref(),
+                    &[escrow.choice_id],
+                    escrow.voter.as_ref(),
+                ],
+                ctx.program_id,
+            );
+            require!(
+                vault_authority.key() == expected_authority
+                    && escrow_accounts[1].key() == expected_vault
+                    && escrow_vault.owner == expected_authority
+                    && escrow_vault.mint == token_mint_key,
+                ErrorCode::InvalidBatchAccounts
+            );
+
+            // Record the settlement before any tokens move
+            escrow.distributed = true;
+            escrow.exit(ctx.program_id)?;
+            ctx.accounts.proposal.open_escrow_count = ctx
+                .accounts
+                .proposal
+                .open_escrow_count
+                .checked_sub(1)
+                .ok_or(ErrorCode::CalculationError)?;
+
+            safe_transfer(
+                &ctx.accounts.token_program,
+                &escrow_vault,
+                &ctx.accounts.creator_token_account,
+                vault_authority.clone(),
                 &[&[
                     b"vault_authority",
-                    proposal.key().as_ref(),
+                    proposal_key.as_ref(),
                     &[escrow.choice_id],
                     escrow.voter.as_ref(),
-                    &[ctx.bumps.vault_authority]
+                    &[authority_bump]
                 ]],
-            ),
+                escrow.locked_amount,
+            )?;
+
+            distributed_count += 1;
+            distributed_amount += escrow.locked_amount;
+        }
+
+        let proposal = &ctx.accounts.proposal;
+        msg!("Distributed {} winning escrows ({} tokens) to {}",
+            distributed_count, distributed_amount, proposal.winner_recipient());
+
+        Ok(())
+    }
+
+    pub fn refund_losing_escrow(ctx: Context<RefundLosingEscrow>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let escrow = &mut ctx.accounts.choice_escrow;
+
+        match proposal.status {
+            // Only escrows for losing choices are refunded once a winner is chosen
+            ProposalStatus::Executed => {
+                require!(proposal.winning_choice.is_some(), ErrorCode::NoWinningChoice);
+                require!(
+                    !proposal.is_winning_choice(escrow.choice_id),
+                    ErrorCode::IsWinningEscrow
+                );
+            }
+            // Every escrow of a rejected proposal goes back to its voter
+            ProposalStatus::Rejected => {}
+            _ => return err!(ErrorCode::ProposalNotExecuted),
+        }
+        require!(escrow.locked_amount > 0, ErrorCode::EmptyEscrow);
+        require!(!escrow.distributed, ErrorCode::AlreadyDistributed);
+
+        // Record the settlement before any tokens move
+        escrow.distributed = true;
+        proposal.open_escrow_count = proposal
+            .open_escrow_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::CalculationError)?;
+
+        // Transfer the tokens back to the voter
+        safe_transfer(
+            &ctx.accounts.token_program,
+            &ctx.accounts.escrow_vault,
+            &ctx.accounts.voter_token_account,
+            ctx.accounts.vault_authority.to_account_info(),
+            &[&[
+                b"vault_authority",
+                proposal.key().as_ref(),
+                &[escrow.choice_id],
+                escrow.voter.as_ref(),
+                &[ctx.bumps.vault_authority]
+            ]],
             escrow.locked_amount,
         )?;
 
@@ -344,6 +1195,307 @@ pub mod community_token_launcher {
 
         Ok(())
     }
+
+    /// Refunds several losing escrows in one call. `remaining_accounts` must be passed as
+    /// (choice_escrow, escrow_vault, vault_authority, voter_token_account) groups, at most
+    /// MAX_REFUND_BATCH_ESCROWS of them. Winning and already settled escrows are skipped.
+    pub fn refund_losing_escrows_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundLosingEscrowsBatch<'info>>,
+    ) -> Result<()> {
+        let remaining_accounts = ctx.remaining_accounts;
+        require!(
+            remaining_accounts.len() % 4 == 0,
+            ErrorCode::InvalidBatchAccounts
+        );
+        require!(
+            remaining_accounts.len() / 4 <= MAX_REFUND_BATCH_ESCROWS,
+            ErrorCode::BatchTooLarge
+        );
+
+        let proposal_key = ctx.accounts.proposal.key();
+        let token_mint_key = ctx.accounts.token_mint.key();
+        if ctx.accounts.proposal.status == ProposalStatus::Executed {
+            require!(
+                ctx.accounts.proposal.winning_choice.is_some(),
+                ErrorCode::NoWinningChoice
+            );
+        }
+
+        let mut refunded_count: u64 = 0;
+        let mut refunded_amount: u64 = 0;
+
+        for escrow_accounts in remaining_accounts.chunks(4) {
+            let mut escrow = Account::<ChoiceEscrow>::try_from(&escrow_accounts[0])?;
+            let escrow_vault = Account::<TokenAccount>::try_from(&escrow_accounts[1])?;
+            let vault_authority = &escrow_accounts[2];
+            let voter_token_account = Account::<TokenAccount>::try_from(&escrow_accounts[3])?;
+
+            require!(escrow.proposal == proposal_key, ErrorCode::InvalidBatchAccounts);
+
+            // Winning escrows of an executed proposal are never refunded
+            let is_winner = ctx.accounts.proposal.status == ProposalStatus::Executed
+                && ctx.accounts.proposal.is_winning_choice(escrow.choice_id);
+            if is_winner || escrow.locked_amount == 0 || escrow.distributed {
+                continue;
+            }
+
+            // The vault must be this escrow's own vault PDA, not just any token account
+            // owned by its authority
+            let (expected_authority, authority_bump) = Pubkey::find_program_address(
+                &[
+                    b"vault_authority",
+                    proposal_key.as_ref(),
+                    &[escrow.choice_id],
+                    escrow.voter.as_ref(),
+                ],
+                ctx.program_id,
+            );
+            let (expected_vault, _) = Pubkey::find_program_address(
+                &[
+                    b"choice_escrow_vault",
+                    proposal_key.as_ref(),
+                    &[escrow.choice_id],
+                    escrow.voter.as_ref(),
+                ],
+                ctx.program_id,
+            );
+            require!(
+                vault_authority.key() == expected_authority
+                    && escrow_accounts[1].key() == expected_vault
+                    && escrow_vault.owner == expected_authority
+                    && escrow_vault.mint == token_mint_key,
+                ErrorCode::InvalidBatchAccounts
+            );
+            require!(
+                voter_token_account.owner == escrow.voter
+                    && voter_token_account.mint == token_mint_key,
+                ErrorCode::InvalidBatchAccounts
+            );
+
+            // Record the settlement before any tokens move
+            escrow.distributed = true;
+            escrow.exit(ctx.program_id)?;
+            ctx.accounts.proposal.open_escrow_count = ctx
+                .accounts
+                .proposal
+                .open_escrow_count
+                .checked_sub(1)
+                .ok_or(ErrorCode::CalculationError)?;
+
+            safe_transfer(
+                &ctx.accounts.token_program,
+                &escrow_vault,
+                &voter_token_account,
+                vault_authority.clone(),
+                &[&[
+                    b"vault_authority",
+                    proposal_key.as_ref(),
+                    &[escrow.choice_id],
+                    escrow.voter.as_ref(),
+                    &[authority_bump]
+                ]],
+                escrow.locked_amount,
+            )?;
+
+            refunded_count += 1;
+            refunded_amount += escrow.locked_amount;
+        }
+
+        msg!("Refunded {} losing escrows ({} tokens) to their voters",
+            refunded_count, refunded_amount);
+
+        Ok(())
+    }
+
+    /// Moves a small leftover balance out of a settled escrow vault to the proposal's winner
+    /// recipient, the same wallet that receives distributed escrows.
+    pub fn sweep_escrow_dust(ctx: Context<SweepEscrowDust>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let escrow = &ctx.accounts.choice_escrow;
+        let dust = ctx.accounts.escrow_vault.amount;
+
+        // The locked tokens themselves must already have been paid out, or withdrawn
+        // entirely through reduce_vote before the vote ended
+        require!(
+            escrow.distributed || escrow.locked_amount == 0,
+            ErrorCode::EscrowNotSettled
+        );
+        require!(dust > 0, ErrorCode::EmptyEscrow);
+        require!(dust <= MAX_ESCROW_DUST, ErrorCode::DustAboveThreshold);
+
+        safe_transfer(
+            &ctx.accounts.token_program,
+            &ctx.accounts.escrow_vault,
+            &ctx.accounts.creator_token_account,
+            ctx.accounts.vault_authority.to_account_info(),
+            &[&[
+                b"vault_authority",
+                proposal.key().as_ref(),
+                &[escrow.choice_id],
+                escrow.voter.as_ref(),
+                &[ctx.bumps.vault_authority]
+            ]],
+            dust,
+        )?;
+
+        msg!("Swept {} dust tokens from escrow of {}", dust, escrow.voter);
+
+        Ok(())
+    }
+
+    pub fn close_choice_escrow(ctx: Context<CloseChoiceEscrow>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let escrow = &ctx.accounts.choice_escrow;
+
+        // Escrows can only be closed once the proposal has been settled
+        require!(
+            proposal.status == ProposalStatus::Executed
+                || proposal.status == ProposalStatus::Rejected,
+            ErrorCode::ProposalNotExecuted
+        );
+
+        let proposal_key = proposal.key();
+        let authority_seeds: &[&[&[u8]]] = &[&[
+            b"vault_authority",
+            proposal_key.as_ref(),
+            &[escrow.choice_id],
+            escrow.voter.as_ref(),
+            &[ctx.bumps.vault_authority]
+        ]];
+
+        // Guard against closing before the locked tokens have been moved out. Once they
+        // have, anything left was sent to the vault directly and goes to the voter, so a
+        // donation cannot block the close.
+        let leftover = ctx.accounts.escrow_vault.amount;
+        if leftover > 0 {
+            require!(
+                escrow.distributed || escrow.locked_amount == 0,
+                ErrorCode::EscrowNotEmpty
+            );
+            safe_transfer(
+                &ctx.accounts.token_program,
+                &ctx.accounts.escrow_vault,
+                &ctx.accounts.voter_token_account,
+                ctx.accounts.vault_authority.to_account_info(),
+                authority_seeds,
+                leftover,
+            )?;
+        }
+
+        // Close the empty vault and return its rent to the voter
+        token::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::CloseAccount {
+                    account: ctx.accounts.escrow_vault.to_account_info(),
+                    destination: ctx.accounts.voter.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                authority_seeds,
+            ),
+        )?;
+
+        // The escrow and receipt accounts are closed to the voter by Anchor
+        msg!("Closed choice escrow for voter {}", escrow.voter);
+
+        Ok(())
+    }
+}
+
+// Helpers
+
+/// Transfers SPL tokens after checking the common failure modes up front, so
+/// callers get a specific error instead of an opaque token program failure.
+/// Pass empty `signer_seeds` when the authority signs the transaction itself.
+fn safe_transfer<'info>(
+    token_program: &Program<'info, Token>,
+    from: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    require!(!from.is_frozen() && !to.is_frozen(), ErrorCode::AccountFrozen);
+    require!(from.mint == to.mint, ErrorCode::InvalidTokenMint);
+
+    // The authority must be the owner, or a delegate approved for the amount
+    let available = if from.owner == authority.key() {
+        from.amount
+    } else if from.delegate == COption::Some(authority.key()) {
+        from.amount.min(from.delegated_amount)
+    } else {
+        return err!(ErrorCode::InvalidTokenAccountOwner);
+    };
+    require!(available >= amount, ErrorCode::InsufficientTokens);
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: from.to_account_info(),
+                to: to.to_account_info(),
+                authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+/// Locks `amount` tokens from the voter's token account into the escrow for
+/// `choice_id` and records the vote. `authority` signs the token transfer and may
+/// be the voter or an SPL delegate of their token account.
+#[allow(clippy::too_many_arguments)]
+fn cast_vote<'info>(
+    token_program: &Program<'info, Token>,
+    proposal: &mut Account<'info, MultiChoiceProposal>,
+    voter_receipt: &mut Account<'info, VoterReceipt>,
+    choice_escrow: &mut Account<'info, ChoiceEscrow>,
+    voter_token_account: &Account<'info, TokenAccount>,
+    choice_escrow_vault: &Account<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    voter: Pubkey,
+    choice_id: u8,
+    amount: u64,
+) -> Result<()> {
+    // An Active proposal past its end time is awaiting execution and takes no new votes
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(current_time >= proposal.start_at, ErrorCode::VotingNotStarted);
+    require!(current_time <= proposal.ends_at, ErrorCode::VotingEnded);
+
+    // A voter may top up their existing choice but not back a second one
+    if voter_receipt.voter == Pubkey::default() {
+        voter_receipt.voter = voter;
+        voter_receipt.proposal = proposal.key();
+        voter_receipt.choice_id = choice_id;
+    } else {
+        require!(voter_receipt.choice_id == choice_id, ErrorCode::AlreadyVoted);
+    }
+
+    // SPL transfer from voter → choice escrow vault
+    safe_transfer(
+        token_program,
+        voter_token_account,
+        choice_escrow_vault,
+        authority,
+        &[],
+        amount,
+    )?;
+
+    // Track escrows that still hold funds so settlement progress can be queried
+    if choice_escrow.locked_amount == 0 {
+        proposal.open_escrow_count += 1;
+    }
+
+    choice_escrow.voter = voter;
+    choice_escrow.proposal = proposal.key();
+    choice_escrow.choice_id = choice_id;
+    choice_escrow.locked_amount += amount;
+    choice_escrow.credited_power += amount;
+    choice_escrow.voted_at = current_time;
+
+    // Update proposal vote counts for this choice
+    proposal.update_vote_count(choice_id, amount)
 }
 
 // Data Structures
@@ -353,6 +1505,10 @@ pub struct ChoiceEscrow {
     pub proposal: Pubkey,
     pub choice_id: u8,
     pub locked_amount: u64,
+    pub distributed: bool,
+    pub bump: u8,
+    pub voted_at: i64,
+    pub credited_power: u64,
 }
 
 impl ChoiceEscrow {
@@ -361,7 +1517,65 @@ impl ChoiceEscrow {
     /// + 32 bytes for `proposal`
     /// +  1 byte for `choice_id`
     /// +  8 bytes for `locked_amount`
-    pub const LEN: usize = 8 + 32 + 32 + 1 + 8;
+    /// +  1 byte for `distributed`
+    /// +  1 byte for `bump`
+    /// +  8 bytes for `voted_at`
+    /// +  8 bytes for `credited_power`
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 1 + 1 + 8 + 8;
+}
+
+#[account]
+pub struct VoterReceipt {
+    pub voter: Pubkey,
+    pub proposal: Pubkey,
+    pub choice_id: u8,
+}
+
+impl VoterReceipt {
+    /// 8 bytes for the account discriminator
+    /// + 32 bytes for `voter`
+    /// + 32 bytes for `proposal`
+    /// +  1 byte for `choice_id`
+    pub const LEN: usize = 8 + 32 + 32 + 1;
+}
+
+#[account]
+pub struct HolderSnapshot {
+    pub holder: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub recorded_at: i64,
+}
+
+impl HolderSnapshot {
+    /// 8 bytes for the account discriminator
+    /// + 32 bytes for `holder`
+    /// + 32 bytes for `token_mint`
+    /// +  8 bytes for `amount`
+    /// +  8 bytes for `recorded_at`
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8;
+}
+
+#[account]
+pub struct VoteDelegation {
+    pub delegator: Pubkey,
+    pub token_mint: Pubkey,
+    pub delegate: Pubkey,
+}
+
+impl VoteDelegation {
+    /// 8 bytes for the account discriminator
+    /// + 32 bytes for `delegator`
+    /// + 32 bytes for `token_mint`
+    /// + 32 bytes for `delegate` (default when cleared)
+    pub const LEN: usize = 8 + 32 + 32 + 32;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum TieBreak {
+    LowestIndex,
+    Reject,
+    ExtendVoting,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -380,6 +1594,7 @@ pub struct TokenRegistry {
     pub launch_timestamp: i64,
     pub governance_enabled: bool,
     pub is_initialized: bool,
+    pub pending_authority: Option<Pubkey>,
 }
 
 impl TokenRegistry {
@@ -387,12 +1602,40 @@ impl TokenRegistry {
         + 32   // authority
         + 32   // token_mint
         + 4    // token_name length prefix
-        + 32   // token_name data
+        + MAX_TOKEN_NAME_LENGTH   // token_name data
         + 4    // token_symbol length prefix
-        + 8    // token_symbol data
+        + MAX_TOKEN_SYMBOL_LENGTH // token_symbol data
         + 8    // launch_timestamp
         + 1    // governance_enabled
-        + 1;   // is_initialized
+        + 1    // is_initialized
+        + 33;  // pending_authority
+}
+
+/// Program-wide header for the registered token mints, which are listed across
+/// `RegistryIndexPage` accounts numbered from 0 to `current_page`.
+#[account]
+pub struct RegistryIndex {
+    pub current_page: u32,
+    pub total_mints: u64,
+}
+
+impl RegistryIndex {
+    pub const LEN: usize = 8  // discriminator
+        + 4   // current_page
+        + 8;  // total_mints
+}
+
+#[account]
+pub struct RegistryIndexPage {
+    pub page: u32,
+    pub mints: Vec<Pubkey>,
+}
+
+impl RegistryIndexPage {
+    pub const LEN: usize = 8  // discriminator
+        + 4   // page
+        + 4   // mints: length prefix
+        + REGISTRY_INDEX_PAGE_SIZE * 32; // mints
 }
 
 #[account]
@@ -405,9 +1648,20 @@ pub struct Governance {
     pub min_vote_threshold: u64,
     pub proposal_threshold: u64,
     pub proposal_threshold_percentage: u8,
+    pub quorum_percentage: u8,
+    pub max_lifetime_proposals: u64,
+    pub tie_break: TieBreak,
+    pub execution_timelock: i64,
+    pub per_choice_threshold: u64,
+    pub min_hold_duration: i64,
+    pub active_proposal_count: u64,
+    pub max_active_proposals: u64,
+    pub min_vote_amount: u64,
+    pub max_voting_period: i64,
     pub name: String,
     pub is_active: bool,
     pub created_at: i64,
+    pub bump: u8,
 }
 
 impl Governance {
@@ -420,10 +1674,30 @@ impl Governance {
         + 8   // min_vote_threshold
         + 8   // proposal_threshold
         + 1   // proposal_threshold_percentage
+        + 1   // quorum_percentage
+        + 8   // max_lifetime_proposals
+        + 1   // tie_break (enum)
+        + 8   // execution_timelock
+        + 8   // per_choice_threshold
+        + 8   // min_hold_duration
+        + 8   // active_proposal_count
+        + 8   // max_active_proposals
+        + 8   // min_vote_amount
+        + 8   // max_voting_period
         + 4   // name: length prefix
         + 32  // name (max length)
         + 1   // is_active
-        + 8;  // created_at
+        + 8   // created_at
+        + 1;  // bump
+
+    // Called whenever a proposal leaves the Active status
+    pub fn release_active_proposal(&mut self) -> Result<()> {
+        self.active_proposal_count = self
+            .active_proposal_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(())
+    }
 }
 
 #[account]
@@ -440,9 +1714,37 @@ pub struct MultiChoiceProposal {
     pub created_at: i64,
     pub ends_at: i64,
     pub winning_choice: Option<u8>,
+    pub winner_recipient_override: Option<Pubkey>,
+    pub abstain_choice: Option<u8>,
+    pub supply_snapshot: u64,
+    pub start_at: i64,
+    pub open_escrow_count: u64,
+    pub executed_at: i64,
+    pub winners_count: u8,
+    pub winning_choices: Vec<u8>,
+    pub vetoed_by: Option<Pubkey>,
+    // Informational only: 0 = General, 1 = Treasury, 2 = Parameter, 3 = Social
+    pub category: u8,
 }
 
 impl MultiChoiceProposal {
+    // Earliest time winning escrows may be distributed after execution
+    pub fn distribution_unlocks_at(&self, execution_timelock: i64) -> Result<i64> {
+        self.executed_at
+            .checked_add(execution_timelock)
+            .ok_or(ErrorCode::CalculationError.into())
+    }
+
+    pub fn is_winning_choice(&self, choice_id: u8) -> bool {
+        self.winning_choices.contains(&choice_id)
+    }
+
+    // Wallet that receives winning escrows: the override if set, otherwise the token creator.
+    // Both are fixed when the proposal is created and do not follow a later registry transfer.
+    pub fn winner_recipient(&self) -> Pubkey {
+        self.winner_recipient_override.unwrap_or(self.token_creator)
+    }
+
     // Helper method to update vote count for a specific choice
     pub fn update_vote_count(&mut self, choice_id: u8, amount: u64) -> Result<()> {
         require!(
@@ -454,31 +1756,66 @@ impl MultiChoiceProposal {
         Ok(())
     }
 
+    // Total participation across all choices, including abstain
+    pub fn total_votes(&self) -> u64 {
+        self.choice_vote_counts.iter().sum()
+    }
+
+    // Quorum is measured against the supply captured when the proposal was created
+    pub fn meets_quorum(&self, quorum_percentage: u8) -> bool {
+        self.total_votes() as u128 * 100
+            >= self.supply_snapshot as u128 * quorum_percentage as u128
+    }
+
+    // Helper method to remove votes from a specific choice
+    pub fn decrement_vote_count(&mut self, choice_id: u8, amount: u64) -> Result<()> {
+        require!(
+            (choice_id as usize) < self.choices.len(),
+            ErrorCode::InvalidChoiceId
+        );
+
+        let count = &mut self.choice_vote_counts[choice_id as usize];
+        *count = count.checked_sub(amount).ok_or(ErrorCode::CalculationError)?;
+        Ok(())
+    }
+
     pub const BASE_LEN: usize = 8  // discriminator
         + 8   // id
         + 32  // governance
         + 32  // proposer
         + 32  // token_creator
         + 4   // title length prefix
-        + 100 // title (max length)
+        + MAX_TITLE_LENGTH       // title
         + 4   // description length prefix
-        + 500 // description (max length)
+        + MAX_DESCRIPTION_LENGTH // description
         // Vectors have variable size
         + 4   // choices vec length prefix
         + 4   // choice_vote_counts vec length prefix
         + 1   // status (enum)
         + 8   // created_at
         + 8   // ends_at
-        + 2;  // Option<u8> for winning_choice
+        + 2   // Option<u8> for winning_choice
+        + 33  // Option<Pubkey> for winner_recipient_override
+        + 2   // Option<u8> for abstain_choice
+        + 8   // supply_snapshot
+        + 8   // open_escrow_count
+        + 8   // start_at
+        + 8   // executed_at
+        + 1   // winners_count
+        + 4   // winning_choices vec length prefix
+        + 33  // Option<Pubkey> for vetoed_by
+        + 1;  // category
 
     // Calculate space needed for a proposal with given number of choices
     pub fn space(num_choices: usize) -> usize {
         // Base length plus space for choices
         Self::BASE_LEN
             // Each choice is a string with prefix
-            + num_choices * (4 + 50)  // Assuming max 50 chars per choice
+            + num_choices * (4 + MAX_CHOICE_LENGTH)
             // Each vote count is a u64
             + num_choices * 8
+            // Each winning choice is a u8
+            + num_choices
     }
 }
 
@@ -491,7 +1828,7 @@ pub struct LockTokensForChoice<'info> {
 
     #[account(
         seeds = [b"governance", token_mint.key().as_ref()],
-        bump
+        bump = governance.bump
     )]
     pub governance: Account<'info, Governance>,
 
@@ -503,7 +1840,16 @@ pub struct LockTokensForChoice<'info> {
     pub proposal: Account<'info, MultiChoiceProposal>,
 
     #[account(
-        init,
+        init_if_needed,
+        payer = voter,
+        space = VoterReceipt::LEN,
+        seeds = [b"voter_receipt", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub voter_receipt: Account<'info, VoterReceipt>,
+
+    #[account(
+        init_if_needed,
         payer = voter,
         space = ChoiceEscrow::LEN,
         seeds = [
@@ -538,7 +1884,7 @@ pub struct LockTokensForChoice<'info> {
     pub vault_authority: UncheckedAccount<'info>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = voter,
         token::mint = token_mint,
         token::authority = vault_authority,
@@ -558,217 +1904,875 @@ pub struct LockTokensForChoice<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(title: String, description: String, choices: Vec<String>, voting_duration: Option<i64>)]
-pub struct CreateMultiChoiceProposal<'info> {
+pub struct SetDelegate<'info> {
     #[account(mut)]
-    pub proposer: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"governance", governance.token_mint.as_ref()],
-        bump,
-        constraint = governance.is_active
-    )]
-    pub governance: Account<'info, Governance>,
-
-    #[account(
-        seeds = [b"token_registry", token_registry.token_mint.as_ref()],
-        bump,
-        constraint = token_registry.token_mint == governance.token_mint
-    )]
-    pub token_registry: Account<'info, TokenRegistry>,
+    pub delegator: Signer<'info>,
 
-    #[account(
-        constraint = token_mint.key() == governance.token_mint
-    )]
     pub token_mint: Account<'info, Mint>,
 
     #[account(
-        init,
-        payer = proposer,
-        // Space calculation is dynamic based on number of choices
-        space = 8 + MultiChoiceProposal::space(MAX_CHOICES),
-        seeds = [b"proposal", governance.key().as_ref(), &governance.proposal_count.to_le_bytes()],
+        init_if_needed,
+        payer = delegator,
+        space = VoteDelegation::LEN,
+        seeds = [b"delegation", token_mint.key().as_ref(), delegator.key().as_ref()],
         bump
     )]
-    pub proposal: Account<'info, MultiChoiceProposal>,
+    pub delegation: Account<'info, VoteDelegation>,
 
-    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ExecuteProposal<'info> {
-    #[account(mut)]
-    pub executor: Signer<'info>,
-
-    #[account(
-        seeds = [b"token_registry", token_registry.token_mint.as_ref()],
-        bump,
-        constraint = token_registry.token_mint == governance.token_mint
-    )]
-    pub token_registry: Account<'info, TokenRegistry>,
+pub struct ClearDelegate<'info> {
+    pub delegator: Signer<'info>,
 
-    #[account(
-        seeds = [b"governance", governance.token_mint.as_ref()],
-        bump
-    )]
-    pub governance: Account<'info, Governance>,
+    pub token_mint: Account<'info, Mint>,
 
     #[account(
         mut,
-        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
-        bump,
-        constraint = proposal.governance == governance.key()
+        seeds = [b"delegation", token_mint.key().as_ref(), delegator.key().as_ref()],
+        bump
     )]
-    pub proposal: Account<'info, MultiChoiceProposal>,
+    pub delegation: Account<'info, VoteDelegation>,
 }
 
 #[derive(Accounts)]
-pub struct DistributeWinningEscrow<'info> {
+#[instruction(amount: u64, choice_id: u8)]
+pub struct LockTokensForChoiceAsDelegate<'info> {
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+
+    /// CHECK: Only used as a key; bound to the delegate through the delegation PDA
+    pub delegator: UncheckedAccount<'info>,
+
     #[account(
-        mut,
-        constraint = executor.key() == proposal.token_creator @ ErrorCode::Unauthorized
+        seeds = [b"delegation", token_mint.key().as_ref(), delegator.key().as_ref()],
+        bump,
+        constraint = delegation.delegate == delegate.key() @ ErrorCode::NoActiveDelegation
     )]
-    pub executor: Signer<'info>,
+    pub delegation: Account<'info, VoteDelegation>,
 
     #[account(
         seeds = [b"governance", token_mint.key().as_ref()],
-        bump
+        bump = governance.bump
     )]
     pub governance: Account<'info, Governance>,
 
     #[account(
-        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
-        bump,
+        mut,
         constraint = proposal.governance == governance.key(),
-        constraint = proposal.status == ProposalStatus::Executed
+        constraint = proposal.status == ProposalStatus::Active
     )]
     pub proposal: Account<'info, MultiChoiceProposal>,
 
     #[account(
+        init_if_needed,
+        payer = delegate,
+        space = VoterReceipt::LEN,
+        seeds = [b"voter_receipt", proposal.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub voter_receipt: Account<'info, VoterReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = delegate,
+        space = ChoiceEscrow::LEN,
         seeds = [
             b"choice_escrow",
             proposal.key().as_ref(),
-            &[choice_escrow.choice_id],
-            choice_escrow.voter.as_ref()
+            &[choice_id],
+            delegator.key().as_ref()
         ],
         bump
     )]
     pub choice_escrow: Account<'info, ChoiceEscrow>,
 
-    /// CHECK: This is a PDA used as token account authority
+    #[account(
+        mut,
+        constraint = voter_token_account.owner == delegator.key(),
+        constraint = voter_token_account.mint == token_mint.key()
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    /// CHECK: This is a PDA used as token account authority
+    #[account(
+        seeds = [
+            b"vault_authority",
+            proposal.key().as_ref(),
+            &[choice_id],
+            delegator.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = delegate,
+        token::mint = token_mint,
+        token::authority = vault_authority,
+        seeds = [
+            b"choice_escrow_vault",
+            proposal.key().as_ref(),
+            &[choice_id],
+            delegator.key().as_ref()
+        ],
+        bump
+    )]
+    pub choice_escrow_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RecordHolding<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        constraint = holder_token_account.owner == holder.key(),
+        constraint = holder_token_account.mint == token_mint.key()
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = HolderSnapshot::LEN,
+        seeds = [b"holder_snapshot", token_mint.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub holder_snapshot: Account<'info, HolderSnapshot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    title: String,
+    description: String,
+    choices: Vec<String>,
+    voting_duration: Option<i64>,
+    winner_recipient_override: Option<Pubkey>,
+    has_abstain: bool
+)]
+pub struct CreateMultiChoiceProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"governance", governance.token_mint.as_ref()],
+        bump = governance.bump,
+        constraint = governance.is_active @ ErrorCode::GovernanceInactive
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        seeds = [b"token_registry", token_registry.token_mint.as_ref()],
+        bump,
+        constraint = token_registry.token_mint == governance.token_mint
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+
+    #[account(
+        constraint = token_mint.key() == governance.token_mint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        constraint = proposer_token_account.owner == proposer.key(),
+        constraint = proposer_token_account.mint == token_mint.key()
+    )]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"holder_snapshot", token_mint.key().as_ref(), proposer.key().as_ref()],
+        bump
+    )]
+    pub holder_snapshot: Option<Account<'info, HolderSnapshot>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        // Space is sized for the submitted choices plus the optional abstain choice
+        space = 8 + MultiChoiceProposal::space((choices.len() + has_abstain as usize).min(MAX_CHOICES)),
+        seeds = [b"proposal", governance.key().as_ref(), &governance.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, MultiChoiceProposal>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"token_registry", token_registry.token_mint.as_ref()],
+        bump,
+        constraint = token_registry.token_mint == governance.token_mint
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+
+    #[account(
+        mut,
+        seeds = [b"governance", governance.token_mint.as_ref()],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        constraint = proposal.governance == governance.key()
+    )]
+    pub proposal: Account<'info, MultiChoiceProposal>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeFailedProposal<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"governance", governance.token_mint.as_ref()],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        constraint = proposal.governance == governance.key()
+    )]
+    pub proposal: Account<'info, MultiChoiceProposal>,
+}
+
+#[derive(Accounts)]
+pub struct VetoProposal<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"governance", governance.token_mint.as_ref()],
+        bump = governance.bump,
+        constraint = governance.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        constraint = proposal.governance == governance.key()
+    )]
+    pub proposal: Account<'info, MultiChoiceProposal>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeWinningEscrow<'info> {
+    #[account(
+        mut,
+        constraint = executor.key() == governance.authority @ ErrorCode::Unauthorized
+    )]
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"governance", token_mint.key().as_ref()],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        constraint = proposal.governance == governance.key(),
+        constraint = proposal.status == ProposalStatus::Executed
+    )]
+    pub proposal: Account<'info, MultiChoiceProposal>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"choice_escrow",
+            proposal.key().as_ref(),
+            &[choice_escrow.choice_id],
+            choice_escrow.voter.as_ref()
+        ],
+        bump = choice_escrow.bump
+    )]
+    pub choice_escrow: Account<'info, ChoiceEscrow>,
+
+    /// CHECK: This is a PDA used as token account authority
+    #[account(
+        seeds = [
+            b"vault_authority",
+            proposal.key().as_ref(),
+            &[choice_escrow.choice_id],
+            choice_escrow.voter.as_ref()
+        ],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"choice_escrow_vault",
+            proposal.key().as_ref(),
+            &[choice_escrow.choice_id],
+            choice_escrow.voter.as_ref()
+        ],
+        bump
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == proposal.winner_recipient(),
+        constraint = creator_token_account.mint == token_mint.key()
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    pub token_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeWinningEscrowsBatch<'info> {
+    #[account(
+        mut,
+        constraint = executor.key() == governance.authority @ ErrorCode::Unauthorized
+    )]
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"governance", token_mint.key().as_ref()],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        constraint = proposal.governance == governance.key(),
+        constraint = proposal.status == ProposalStatus::Executed
+    )]
+    pub proposal: Account<'info, MultiChoiceProposal>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == proposal.winner_recipient(),
+        constraint = creator_token_account.mint == token_mint.key()
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    pub token_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RefundLosingEscrowsBatch<'info> {
+    #[account(
+        mut,
+        constraint = executor.key() == governance.authority @ ErrorCode::Unauthorized
+    )]
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"governance", token_mint.key().as_ref()],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        constraint = proposal.governance == governance.key(),
+        constraint = proposal.status == ProposalStatus::Executed
+            || proposal.status == ProposalStatus::Rejected
+    )]
+    pub proposal: Account<'info, MultiChoiceProposal>,
+
+    pub token_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRegistryIndex<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RegistryIndex::LEN,
+        seeds = [b"registry_index"],
+        bump
+    )]
+    pub registry_index: Account<'info, RegistryIndex>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTokenRegistry<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = TokenRegistry::LEN,
+        seeds = [b"token_registry", token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+
+    #[account(
+        mut,
+        seeds = [b"registry_index"],
+        bump
+    )]
+    pub registry_index: Account<'info, RegistryIndex>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RegistryIndexPage::LEN,
+        seeds = [b"registry_index", &registry_index.current_page.to_le_bytes()],
+        bump
+    )]
+    pub registry_index_page: Account<'info, RegistryIndexPage>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_registry", token_mint.key().as_ref()],
+        bump,
+        constraint = token_registry.authority == authority.key(),
+        constraint = token_registry.is_initialized
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Governance::LEN,
+        seeds = [b"governance", token_mint.key().as_ref()],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGovernanceActive<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"governance", governance.token_mint.as_ref()],
+        bump = governance.bump,
+        constraint = governance.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub governance: Account<'info, Governance>,
+}
+
+#[derive(Accounts)]
+pub struct RefundLosingEscrow<'info> {
+    #[account(
+        mut,
+        constraint = executor.key() == governance.authority @ ErrorCode::Unauthorized
+    )]
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"governance", token_mint.key().as_ref()],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        constraint = proposal.governance == governance.key(),
+        constraint = proposal.status == ProposalStatus::Executed
+            || proposal.status == ProposalStatus::Rejected
+    )]
+    pub proposal: Account<'info, MultiChoiceProposal>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"choice_escrow",
+            proposal.key().as_ref(),
+            &[choice_escrow.choice_id],
+            choice_escrow.voter.as_ref()
+        ],
+        bump = choice_escrow.bump
+    )]
+    pub choice_escrow: Account<'info, ChoiceEscrow>,
+
+    /// CHECK: This is a PDA used as token account authority
+    #[account(
+        seeds = [
+            b"vault_authority",
+            proposal.key().as_ref(),
+            &[choice_escrow.choice_id],
+            choice_escrow.voter.as_ref()
+        ],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"choice_escrow_vault",
+            proposal.key().as_ref(),
+            &[choice_escrow.choice_id],
+            choice_escrow.voter.as_ref()
+        ],
+        bump
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = voter_token_account.owner == choice_escrow.voter,
+        constraint = voter_token_account.mint == token_mint.key()
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    pub token_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReduceVote<'info> {
+    #[account(
+        constraint = voter.key() == choice_escrow.voter @ ErrorCode::Unauthorized
+    )]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"governance", token_mint.key().as_ref()],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        constraint = proposal.governance == governance.key(),
+        constraint = proposal.status == ProposalStatus::Active @ ErrorCode::ProposalNotActive
+    )]
+    pub proposal: Account<'info, MultiChoiceProposal>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"choice_escrow",
+            proposal.key().as_ref(),
+            &[choice_escrow.choice_id],
+            choice_escrow.voter.as_ref()
+        ],
+        bump = choice_escrow.bump
+    )]
+    pub choice_escrow: Account<'info, ChoiceEscrow>,
+
+    /// CHECK: This is a PDA used as token account authority
+    #[account(
+        seeds = [
+            b"vault_authority",
+            proposal.key().as_ref(),
+            &[choice_escrow.choice_id],
+            choice_escrow.voter.as_ref()
+        ],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"choice_escrow_vault",
+            proposal.key().as_ref(),
+            &[choice_escrow.choice_id],
+            choice_escrow.voter.as_ref()
+        ],
+        bump
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = voter_token_account.owner == voter.key(),
+        constraint = voter_token_account.mint == token_mint.key()
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    pub token_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_choice_id: u8)]
+pub struct ChangeVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"governance", token_mint.key().as_ref()],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        constraint = proposal.governance == governance.key(),
+        constraint = proposal.status == ProposalStatus::Active @ ErrorCode::ProposalNotActive
+    )]
+    pub proposal: Account<'info, MultiChoiceProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"voter_receipt", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub voter_receipt: Account<'info, VoterReceipt>,
+
+    #[account(
+        mut,
+        close = voter,
+        seeds = [
+            b"choice_escrow",
+            proposal.key().as_ref(),
+            &[voter_receipt.choice_id],
+            voter.key().as_ref()
+        ],
+        bump = old_choice_escrow.bump
+    )]
+    pub old_choice_escrow: Account<'info, ChoiceEscrow>,
+
+    /// CHECK: This is a PDA used as token account authority
     #[account(
         seeds = [
             b"vault_authority",
             proposal.key().as_ref(),
-            &[choice_escrow.choice_id],
-            choice_escrow.voter.as_ref()
+            &[voter_receipt.choice_id],
+            voter.key().as_ref()
         ],
         bump
     )]
-    pub vault_authority: UncheckedAccount<'info>,
+    pub old_vault_authority: UncheckedAccount<'info>,
 
     #[account(
         mut,
         seeds = [
             b"choice_escrow_vault",
             proposal.key().as_ref(),
-            &[choice_escrow.choice_id],
-            choice_escrow.voter.as_ref()
+            &[voter_receipt.choice_id],
+            voter.key().as_ref()
         ],
         bump
     )]
-    pub escrow_vault: Account<'info, TokenAccount>,
+    pub old_escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = ChoiceEscrow::LEN,
+        seeds = [
+            b"choice_escrow",
+            proposal.key().as_ref(),
+            &[new_choice_id],
+            voter.key().as_ref()
+        ],
+        bump
+    )]
+    pub new_choice_escrow: Account<'info, ChoiceEscrow>,
+
+    /// CHECK: This is a PDA used as token account authority
+    #[account(
+        seeds = [
+            b"vault_authority",
+            proposal.key().as_ref(),
+            &[new_choice_id],
+            voter.key().as_ref()
+        ],
+        bump
+    )]
+    pub new_vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        token::mint = token_mint,
+        token::authority = new_vault_authority,
+        seeds = [
+            b"choice_escrow_vault",
+            proposal.key().as_ref(),
+            &[new_choice_id],
+            voter.key().as_ref()
+        ],
+        bump
+    )]
+    pub new_escrow_vault: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        constraint = creator_token_account.owner == proposal.token_creator,
-        constraint = creator_token_account.mint == token_mint.key()
+        constraint = voter_token_account.owner == voter.key(),
+        constraint = voter_token_account.mint == token_mint.key()
     )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    pub voter_token_account: Account<'info, TokenAccount>,
 
     pub token_mint: Account<'info, Mint>,
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeTokenRegistry<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub token_mint: Account<'info, Mint>,
-    
+pub struct SweepEscrowDust<'info> {
     #[account(
-        init,
-        payer = authority,
-        space = TokenRegistry::LEN,
-        seeds = [b"token_registry", token_mint.key().as_ref()],
-        bump
+        constraint = executor.key() == governance.authority @ ErrorCode::Unauthorized
     )]
-    pub token_registry: Account<'info, TokenRegistry>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub executor: Signer<'info>,
 
-#[derive(Accounts)]
-pub struct InitializeGovernance<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub token_mint: Account<'info, Mint>,
-    
     #[account(
-        mut,
-        seeds = [b"token_registry", token_mint.key().as_ref()],
+        seeds = [b"governance", token_mint.key().as_ref()],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
         bump,
-        constraint = token_registry.authority == authority.key(),
-        constraint = token_registry.is_initialized
+        constraint = proposal.governance == governance.key(),
+        constraint = proposal.status == ProposalStatus::Executed
+            || proposal.status == ProposalStatus::Rejected @ ErrorCode::ProposalNotExecuted
     )]
-    pub token_registry: Account<'info, TokenRegistry>,
-    
+    pub proposal: Account<'info, MultiChoiceProposal>,
+
     #[account(
-        init,
-        payer = authority,
-        space = Governance::LEN,
-        seeds = [b"governance", token_mint.key().as_ref()],
+        seeds = [
+            b"choice_escrow",
+            proposal.key().as_ref(),
+            &[choice_escrow.choice_id],
+            choice_escrow.voter.as_ref()
+        ],
+        bump = choice_escrow.bump
+    )]
+    pub choice_escrow: Account<'info, ChoiceEscrow>,
+
+    /// CHECK: This is a PDA used as token account authority
+    #[account(
+        seeds = [
+            b"vault_authority",
+            proposal.key().as_ref(),
+            &[choice_escrow.choice_id],
+            choice_escrow.voter.as_ref()
+        ],
         bump
     )]
-    pub governance: Account<'info, Governance>,
-    
-    pub system_program: Program<'info, System>,
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"choice_escrow_vault",
+            proposal.key().as_ref(),
+            &[choice_escrow.choice_id],
+            choice_escrow.voter.as_ref()
+        ],
+        bump
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == proposal.winner_recipient(),
+        constraint = creator_token_account.mint == token_mint.key()
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    pub token_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct RefundLosingEscrow<'info> {
+pub struct CloseChoiceEscrow<'info> {
     #[account(
         mut,
-        constraint = executor.key() == proposal.token_creator @ ErrorCode::Unauthorized
+        constraint = voter.key() == choice_escrow.voter @ ErrorCode::Unauthorized
     )]
-    pub executor: Signer<'info>,
+    pub voter: Signer<'info>,
 
     #[account(
-        seeds = [b"governance", token_mint.key().as_ref()],
-        bump
+        seeds = [b"governance", governance.token_mint.as_ref()],
+        bump = governance.bump
     )]
     pub governance: Account<'info, Governance>,
 
     #[account(
         seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
         bump,
-        constraint = proposal.governance == governance.key(),
-        constraint = proposal.status == ProposalStatus::Executed
+        constraint = proposal.governance == governance.key()
     )]
     pub proposal: Account<'info, MultiChoiceProposal>,
 
     #[account(
+        mut,
+        close = voter,
+        seeds = [b"voter_receipt", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub voter_receipt: Account<'info, VoterReceipt>,
+
+    #[account(
+        mut,
+        close = voter,
         seeds = [
             b"choice_escrow",
             proposal.key().as_ref(),
             &[choice_escrow.choice_id],
             choice_escrow.voter.as_ref()
         ],
-        bump
+        bump = choice_escrow.bump
     )]
     pub choice_escrow: Account<'info, ChoiceEscrow>,
 
@@ -798,12 +2802,11 @@ pub struct RefundLosingEscrow<'info> {
 
     #[account(
         mut,
-        constraint = voter_token_account.owner == choice_escrow.voter,
-        constraint = voter_token_account.mint == token_mint.key()
+        constraint = voter_token_account.owner == voter.key(),
+        constraint = voter_token_account.mint == escrow_vault.mint
     )]
     pub voter_token_account: Account<'info, TokenAccount>,
 
-    pub token_mint: Account<'info, Mint>,
     pub token_program: Program<'info, Token>,
 }
 
@@ -812,7 +2815,7 @@ pub struct RefundLosingEscrow<'info> {
 pub struct GetProposal<'info> {
     #[account(
         seeds = [b"governance", governance.token_mint.as_ref()],
-        bump
+        bump = governance.bump
     )]
     pub governance: Account<'info, Governance>,
 
@@ -829,7 +2832,7 @@ pub struct GetProposal<'info> {
 pub struct GetChoice<'info> {
     #[account(
         seeds = [b"governance", governance.token_mint.as_ref()],
-        bump
+        bump = governance.bump
     )]
     pub governance: Account<'info, Governance>,
 
@@ -841,6 +2844,88 @@ pub struct GetChoice<'info> {
     pub proposal: Account<'info, MultiChoiceProposal>,
 }
 
+#[derive(Accounts)]
+pub struct UpdateTokenInfo<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_registry", token_registry.token_mint.as_ref()],
+        bump,
+        constraint = token_registry.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeRegistryTransfer<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_registry", token_registry.token_mint.as_ref()],
+        bump,
+        constraint = token_registry.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptRegistryTransfer<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_registry", token_registry.token_mint.as_ref()],
+        bump,
+        constraint = token_registry.pending_authority == Some(new_authority.key())
+            @ ErrorCode::NotPendingAuthority
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+
+    #[account(
+        mut,
+        seeds = [b"governance", token_registry.token_mint.as_ref()],
+        bump
+    )]
+    pub governance: Option<Account<'info, Governance>>,
+}
+
+#[derive(Accounts)]
+pub struct GetTokenRegistry<'info> {
+    #[account(
+        seeds = [b"token_registry", token_registry.token_mint.as_ref()],
+        bump
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct CheckTokenHolder<'info> {
+    #[account(
+        seeds = [b"token_registry", token_registry.token_mint.as_ref()],
+        bump
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+
+    #[account(
+        constraint = token_account.mint == token_registry.token_mint
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RegistryInfo {
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_name: String,
+    pub token_symbol: String,
+    pub launch_timestamp: i64,
+    pub governance_enabled: bool,
+    pub is_initialized: bool,
+    pub pending_authority: Option<Pubkey>,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct ProposalData {
     pub id: u64,
@@ -854,6 +2939,58 @@ pub struct ProposalData {
     pub created_at: i64,
     pub ends_at: i64,
     pub winning_choice: Option<u8>,
+    pub winner_recipient_override: Option<Pubkey>,
+    pub abstain_choice: Option<u8>,
+    pub start_at: i64,
+    pub winners_count: u8,
+    pub winning_choices: Vec<u8>,
+    pub vetoed_by: Option<Pubkey>,
+    pub category: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposalResults {
+    pub id: u64,
+    pub status: ProposalStatus,
+    pub winning_choice: Option<u8>,
+    pub winning_choices: Vec<u8>,
+    pub total_votes: u64,
+    pub choice_vote_counts: Vec<u64>,
+    pub category: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum ExecutionBlocker {
+    VotingNotEnded,
+    ProposalNotActive,
+    VoteThresholdNotMet,
+    QuorumNotMet,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExecutionStatus {
+    pub voting_ended: bool,
+    pub threshold_met: bool,
+    pub quorum_met: bool,
+    pub already_executed: bool,
+    pub executable: bool,
+    pub blocking_reason: Option<ExecutionBlocker>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum VotingPhase {
+    Pending,
+    Voting,
+    Ended,
+    Executed,
+    Rejected,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposalPhase {
+    pub phase: VotingPhase,
+    pub seconds_until_start: i64,
+    pub seconds_until_end: i64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -890,4 +3027,92 @@ pub enum ErrorCode {
     IsWinningEscrow,
     #[msg("Voting duration must be at least 60 seconds (1 minute)")]
     VotingDurationTooShort,
+    #[msg("Invalid governance settings")]
+    InvalidGovernanceSettings,
+    #[msg("Voter has already voted for a different choice on this proposal")]
+    AlreadyVoted,
+    #[msg("Escrow vault still holds tokens")]
+    EscrowNotEmpty,
+    #[msg("Insufficient tokens for this transfer")]
+    InsufficientTokens,
+    #[msg("Token account is frozen")]
+    AccountFrozen,
+    #[msg("Transfer authority does not own the token account")]
+    InvalidTokenAccountOwner,
+    #[msg("Token accounts are for different mints")]
+    InvalidTokenMint,
+    #[msg("Proposal data exceeds the allocated account space")]
+    InputTooLong,
+    #[msg("Total votes are below the minimum vote threshold")]
+    VoteThresholdNotMet,
+    #[msg("Quorum has not been met")]
+    QuorumNotMet,
+    #[msg("Calculation overflow")]
+    CalculationError,
+    #[msg("Voting period has ended")]
+    VotingEnded,
+    #[msg("Cannot reduce a vote by more than the locked amount")]
+    ReductionExceedsLockedAmount,
+    #[msg("Escrow has no locked tokens")]
+    EmptyEscrow,
+    #[msg("Voting has not started yet")]
+    VotingNotStarted,
+    #[msg("Proposal start time cannot be in the past")]
+    InvalidStartTime,
+    #[msg("Batch accounts are missing or do not match the proposal")]
+    InvalidBatchAccounts,
+    #[msg("Too many escrows in one batch")]
+    BatchTooLarge,
+    #[msg("Proposer does not hold enough tokens to create a proposal")]
+    InsufficientTokensToPropose,
+    #[msg("Governance has reached its lifetime proposal limit")]
+    ProposalLimitReached,
+    #[msg("Proposal met its participation requirements and must be executed")]
+    ProposalNotFailed,
+    #[msg("Proposal account state is corrupt")]
+    CorruptProposalState,
+    #[msg("Proposal title is too long")]
+    TitleTooLong,
+    #[msg("Proposal description is too long")]
+    DescriptionTooLong,
+    #[msg("Proposal choice is too long")]
+    ChoiceTooLong,
+    #[msg("Invalid delegate")]
+    InvalidDelegate,
+    #[msg("Signer is not the active delegate for this voter")]
+    NoActiveDelegation,
+    #[msg("Execution timelock has not elapsed")]
+    TimelockActive,
+    #[msg("New choice is the same as the current choice")]
+    ChoiceUnchanged,
+    #[msg("Escrow has already been distributed")]
+    AlreadyDistributed,
+    #[msg("Winners count must be between 1 and the number of choices")]
+    InvalidWinnersCount,
+    #[msg("A holding snapshot is required to create proposals")]
+    HoldingSnapshotRequired,
+    #[msg("Holding snapshot is too recent")]
+    HoldingTooRecent,
+    #[msg("Escrow has not been distributed or refunded yet")]
+    EscrowNotSettled,
+    #[msg("Vault balance is too large to sweep as dust")]
+    DustAboveThreshold,
+    #[msg("Signer is not the pending registry authority")]
+    NotPendingAuthority,
+    #[msg("Governance has reached its active proposal limit")]
+    TooManyActiveProposals,
+    #[msg("Proposal choices cannot be empty")]
+    EmptyChoice,
+    #[msg("Proposal choices must be unique")]
+    DuplicateChoice,
+    #[msg("Token name is too long")]
+    NameTooLong,
+    #[msg("Token symbol is too long")]
+    SymbolTooLong,
+    #[msg("Vote amount is below the governance minimum")]
+    VoteAmountTooSmall,
+    #[msg("Reduction amount must be greater than zero")]
+    ZeroReduction,
+    #[msg("Governance account must be passed to transfer registry authority")]
+    GovernanceAccountRequired,
 }
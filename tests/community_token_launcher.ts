@@ -10,8 +10,13 @@ import {
 } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
+  approve,
+  createAccount,
   createMint,
   mintTo,
+  transfer,
+  freezeAccount,
+  getAssociatedTokenAddressSync,
   getOrCreateAssociatedTokenAccount,
 } from "@solana/spl-token";
 import { expect } from "chai";
@@ -41,6 +46,15 @@ describe("community_token_launcher", () => {
   const MIN_VOTE_THRESHOLD = new BN(100); // 100 tokens as threshold
   const PROPOSAL_THRESHOLD = new BN(1000);
   const PROPOSAL_THRESHOLD_PERCENTAGE = 1; // 1%
+  const QUORUM_PERCENTAGE = 0; // No supply-based quorum
+  const MAX_LIFETIME_PROPOSALS = new BN(0); // Unlimited
+  const TIE_BREAK = { lowestIndex: {} };
+  const EXECUTION_TIMELOCK = new BN(0); // Distribute immediately after execution
+  const PER_CHOICE_THRESHOLD = new BN(0); // No floor on the winning choice
+  const MIN_HOLD_DURATION = new BN(0); // No holding snapshot required
+  const MAX_ACTIVE_PROPOSALS = new BN(0); // Unlimited
+  const MIN_VOTE_AMOUNT = new BN(0); // Any vote size accepted
+  const MAX_VOTING_PERIOD = new BN(0); // No ceiling on proposal duration overrides
   const GOVERNANCE_NAME = "Test Governance";
 
   // Test data
@@ -49,6 +63,7 @@ describe("community_token_launcher", () => {
   let tokenRegistryBump: number;
   let governancePDA: PublicKey;
   let governanceBump: number;
+  let registryIndexPDA: PublicKey;
   let proposalPDA: PublicKey;
   let proposalBump: number;
   let creatorTokenAccount: PublicKey;
@@ -63,6 +78,266 @@ describe("community_token_launcher", () => {
   let vaultAuthorityBump1: number;
   let choiceEscrowVaultPDA1: PublicKey;
   let choiceEscrowVaultBump1: number;
+  let voterReceiptPDA1: PublicKey;
+
+  // A token with its registry and governance PDAs
+  interface Community {
+    mint: PublicKey;
+    registry: PublicKey;
+    governance: PublicKey;
+  }
+
+  const mainCommunity = (): Community => ({
+    mint: tokenMint,
+    registry: tokenRegistryPDA,
+    governance: governancePDA,
+  });
+
+  // Optional settings for a freshly created community
+  interface CommunityOptions {
+    freezeAuthority?: PublicKey;
+    proposalThresholdPercentage?: number;
+    quorumPercentage?: number;
+    maxLifetimeProposals?: number;
+    tieBreak?: object;
+    executionTimelock?: number;
+    perChoiceThreshold?: BN;
+    minHoldDuration?: number;
+    maxActiveProposals?: number;
+    minVoteAmount?: BN;
+    maxVotingPeriod?: number;
+    // Whole-token balances minted before governance is initialized
+    holders?: [PublicKey, number][];
+  }
+
+  // Registry index page that the next token registration appends to
+  const registryIndexPage = (page: number): PublicKey => {
+    const pageBytes = Buffer.alloc(4);
+    pageBytes.writeUInt32LE(page);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("registry_index"), pageBytes],
+      program.programId
+    )[0];
+  };
+
+  const currentRegistryIndexPage = async (): Promise<PublicKey> => {
+    const registryIndex = await program.account.registryIndex.fetch(registryIndexPDA);
+    return registryIndexPage(registryIndex.currentPage);
+  };
+
+  // Create a fresh mint along with its token registry and governance
+  const createCommunity = async (
+    name: string,
+    options: CommunityOptions = {}
+  ): Promise<Community> => {
+    const mint = await createMint(
+      provider.connection,
+      tokenCreator,
+      tokenCreator.publicKey,
+      options.freezeAuthority ?? null,
+      6
+    );
+
+    const [registry] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_registry"), mint.toBuffer()],
+      program.programId
+    );
+    const [governance] = PublicKey.findProgramAddressSync(
+      [Buffer.from("governance"), mint.toBuffer()],
+      program.programId
+    );
+
+    for (const [owner, tokens] of options.holders ?? []) {
+      await fundWallet({ mint, registry, governance }, owner, tokens);
+    }
+
+    await program.methods
+      .initializeTokenRegistry(name, "TEST")
+      .accounts({
+        authority: tokenCreator.publicKey,
+        tokenMint: mint,
+        tokenRegistry: registry,
+        registryIndex: registryIndexPDA,
+        registryIndexPage: await currentRegistryIndexPage(),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([tokenCreator])
+      .rpc();
+
+    await program.methods
+      .initializeGovernance(
+        VOTING_PERIOD,
+        MIN_VOTE_THRESHOLD,
+        PROPOSAL_THRESHOLD,
+        options.proposalThresholdPercentage ?? 0,
+        options.quorumPercentage ?? 0,
+        new BN(options.maxLifetimeProposals ?? 0),
+        options.tieBreak ?? TIE_BREAK,
+        new BN(options.executionTimelock ?? 0),
+        options.perChoiceThreshold ?? PER_CHOICE_THRESHOLD,
+        new BN(options.minHoldDuration ?? 0),
+        new BN(options.maxActiveProposals ?? 0),
+        options.minVoteAmount ?? MIN_VOTE_AMOUNT,
+        new BN(options.maxVotingPeriod ?? 0),
+        name
+      )
+      .accounts({
+        authority: tokenCreator.publicKey,
+        tokenMint: mint,
+        tokenRegistry: registry,
+        governance: governance,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([tokenCreator])
+      .rpc();
+
+    return { mint, registry, governance };
+  };
+
+  // Mint whole tokens of a community to a wallet and return its token account
+  const fundWallet = async (
+    community: Community,
+    owner: PublicKey,
+    tokens: number
+  ): Promise<PublicKey> => {
+    const tokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        tokenCreator,
+        community.mint,
+        owner
+      )
+    ).address;
+
+    await mintTo(
+      provider.connection,
+      tokenCreator,
+      community.mint,
+      tokenAccount,
+      tokenCreator.publicKey,
+      tokens * Math.pow(10, 6)
+    );
+
+    return tokenAccount;
+  };
+
+  // Derive the proposal PDA for a governance and proposal ID
+  const findProposalPDA = (governance: PublicKey, id: number): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("proposal"),
+        governance.toBuffer(),
+        new anchor.BN(id).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+
+  // Derive every PDA involved in a voter's vote on a choice
+  const findVotePDAs = (proposal: PublicKey, choiceId: number, voter: PublicKey) => {
+    const find = (prefix: string) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from(prefix), proposal.toBuffer(), Buffer.from([choiceId]), voter.toBuffer()],
+        program.programId
+      )[0];
+
+    return {
+      choiceEscrow: find("choice_escrow"),
+      vaultAuthority: find("vault_authority"),
+      choiceEscrowVault: find("choice_escrow_vault"),
+      voterReceipt: PublicKey.findProgramAddressSync(
+        [Buffer.from("voter_receipt"), proposal.toBuffer(), voter.toBuffer()],
+        program.programId
+      )[0],
+    };
+  };
+
+  // Optional arguments for createMultiChoiceProposal
+  interface ProposalOptions {
+    description?: string;
+    votingDuration?: BN;
+    winnerRecipientOverride?: PublicKey;
+    hasAbstain?: boolean;
+    startAt?: BN;
+    winnersCount?: number;
+    category?: number;
+    holderSnapshot?: PublicKey;
+  }
+
+  // Create a proposal on a community's governance and return its PDA
+  const createProposal = async (
+    community: Community,
+    proposer: Keypair,
+    title: string,
+    choices: string[],
+    options: ProposalOptions = {}
+  ): Promise<PublicKey> => {
+    const governanceAccount = await program.account.governance.fetch(community.governance);
+    const proposal = findProposalPDA(
+      community.governance,
+      governanceAccount.proposalCount.toNumber()
+    );
+
+    await program.methods
+      .createMultiChoiceProposal(
+        title,
+        options.description ?? `${title} description`,
+        choices,
+        options.votingDuration ?? null,
+        options.winnerRecipientOverride ?? null,
+        options.hasAbstain ?? false,
+        options.startAt ?? null,
+        options.winnersCount ?? 1,
+        options.category ?? 0
+      )
+      .accounts({
+        proposer: proposer.publicKey,
+        governance: community.governance,
+        tokenRegistry: community.registry,
+        tokenMint: community.mint,
+        proposerTokenAccount: getAssociatedTokenAddressSync(community.mint, proposer.publicKey),
+        holderSnapshot: options.holderSnapshot ?? null,
+        proposal: proposal,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([proposer])
+      .rpc();
+
+    return proposal;
+  };
+
+  // Lock a voter's tokens into the escrow for a choice
+  const lockTokens = async (
+    community: Community,
+    proposal: PublicKey,
+    voter: Keypair,
+    voterTokenAccount: PublicKey,
+    choiceId: number,
+    amount: BN
+  ) => {
+    const pdas = findVotePDAs(proposal, choiceId, voter.publicKey);
+
+    await program.methods
+      .lockTokensForChoice(amount, choiceId)
+      .accounts({
+        voter: voter.publicKey,
+        governance: community.governance,
+        proposal: proposal,
+        voterReceipt: pdas.voterReceipt,
+        choiceEscrow: pdas.choiceEscrow,
+        voterTokenAccount: voterTokenAccount,
+        tokenMint: community.mint,
+        vaultAuthority: pdas.vaultAuthority,
+        choiceEscrowVault: pdas.choiceEscrowVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([voter])
+      .rpc();
+
+    return pdas;
+  };
 
   before(async () => {
     // Airdrop SOL to test accounts and await confirmations
@@ -174,6 +449,22 @@ describe("community_token_launcher", () => {
       [Buffer.from("governance"), tokenMint.toBuffer()],
       program.programId
     );
+
+    // The registry index is a program-wide singleton created once
+    [registryIndexPDA] = await PublicKey.findProgramAddress(
+      [Buffer.from("registry_index")],
+      program.programId
+    );
+
+    await program.methods
+      .initializeRegistryIndex()
+      .accounts({
+        payer: tokenCreator.publicKey,
+        registryIndex: registryIndexPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([tokenCreator])
+      .rpc();
   });
 
   describe("Token Registry and Governance Setup", () => {
@@ -184,6 +475,8 @@ describe("community_token_launcher", () => {
           authority: tokenCreator.publicKey,
           tokenMint: tokenMint,
           tokenRegistry: tokenRegistryPDA,
+          registryIndex: registryIndexPDA,
+          registryIndexPage: await currentRegistryIndexPage(),
           systemProgram: SystemProgram.programId,
         })
         .signers([tokenCreator])
@@ -206,6 +499,15 @@ describe("community_token_launcher", () => {
           MIN_VOTE_THRESHOLD,
           PROPOSAL_THRESHOLD,
           PROPOSAL_THRESHOLD_PERCENTAGE,
+          QUORUM_PERCENTAGE,
+          MAX_LIFETIME_PROPOSALS,
+          TIE_BREAK,
+          EXECUTION_TIMELOCK,
+          PER_CHOICE_THRESHOLD,
+          MIN_HOLD_DURATION,
+          MAX_ACTIVE_PROPOSALS,
+          MIN_VOTE_AMOUNT,
+          MAX_VOTING_PERIOD,
           GOVERNANCE_NAME
         )
         .accounts({
@@ -227,6 +529,15 @@ describe("community_token_launcher", () => {
       expect(governanceAccount.minVoteThreshold.toNumber()).to.equal(MIN_VOTE_THRESHOLD.toNumber());
       expect(governanceAccount.proposalThreshold.toNumber()).to.equal(PROPOSAL_THRESHOLD.toNumber());
       expect(governanceAccount.proposalThresholdPercentage).to.equal(PROPOSAL_THRESHOLD_PERCENTAGE);
+      expect(governanceAccount.quorumPercentage).to.equal(QUORUM_PERCENTAGE);
+      expect(governanceAccount.maxLifetimeProposals.toNumber()).to.equal(0);
+      expect(governanceAccount.tieBreak.lowestIndex).to.exist;
+      expect(governanceAccount.executionTimelock.toNumber()).to.equal(0);
+      expect(governanceAccount.perChoiceThreshold.toNumber()).to.equal(0);
+      expect(governanceAccount.minHoldDuration.toNumber()).to.equal(0);
+      expect(governanceAccount.maxActiveProposals.toNumber()).to.equal(0);
+      expect(governanceAccount.minVoteAmount.toNumber()).to.equal(0);
+      expect(governanceAccount.maxVotingPeriod.toNumber()).to.equal(0);
       expect(governanceAccount.name).to.equal(GOVERNANCE_NAME);
       expect(governanceAccount.isActive).to.be.true;
       expect(governanceAccount.proposalCount.toNumber()).to.equal(0);
@@ -235,6 +546,25 @@ describe("community_token_launcher", () => {
       const tokenRegistryAccount = await program.account.tokenRegistry.fetch(tokenRegistryPDA);
       expect(tokenRegistryAccount.governanceEnabled).to.be.true;
     });
+
+    it("Should return the token registry record", async () => {
+      const registryInfo = await program.methods
+        .getTokenRegistry()
+        .accounts({
+          tokenRegistry: tokenRegistryPDA,
+        })
+        .view();
+
+      const tokenRegistryAccount = await program.account.tokenRegistry.fetch(tokenRegistryPDA);
+      expect(registryInfo.authority.toString()).to.equal(tokenRegistryAccount.authority.toString());
+      expect(registryInfo.tokenMint.toString()).to.equal(tokenMint.toString());
+      expect(registryInfo.tokenName).to.equal(TOKEN_NAME);
+      expect(registryInfo.tokenSymbol).to.equal(TOKEN_SYMBOL);
+      expect(registryInfo.launchTimestamp.toNumber()).to.equal(tokenRegistryAccount.launchTimestamp.toNumber());
+      expect(registryInfo.governanceEnabled).to.be.true;
+      expect(registryInfo.isInitialized).to.be.true;
+      expect(registryInfo.pendingAuthority).to.be.null;
+    });
   });
 
   describe("Proposals and Voting", () => {
@@ -264,13 +594,20 @@ describe("community_token_launcher", () => {
             proposalTitle,
             proposalDescription,
             proposalChoices,
-            null // null for default duration
+            null, // null for default duration
+            null, // no winner recipient override
+            false, // no abstain choice
+            null, // voting opens immediately
+            1, // single winner
+            0 // general category
           )
           .accounts({
             proposer: voter1.publicKey,
             governance: governancePDA,
             tokenRegistry: tokenRegistryPDA,
             tokenMint: tokenMint,
+            proposerTokenAccount: voter1TokenAccount,
+            holderSnapshot: null,
             proposal: proposalPDA,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -294,6 +631,9 @@ describe("community_token_launcher", () => {
         expect(proposalAccount.choiceVoteCounts.length).to.equal(proposalChoices.length);
         expect(proposalAccount.winningChoice).to.be.null;
         
+        // Voting opens immediately by default
+        expect(proposalAccount.startAt.toString()).to.equal(proposalAccount.createdAt.toString());
+
         // Verify that the ends_at is set to created_at + governance voting period
         expect(proposalAccount.endsAt.toString()).to.equal(
           proposalAccount.createdAt.add(governanceAccount.votingPeriod).toString()
@@ -333,13 +673,20 @@ describe("community_token_launcher", () => {
             "Custom Duration Proposal",
             "This proposal has a custom voting period",
             proposalChoices,
-            customDuration
+            customDuration,
+            null,
+            false,
+            null,
+            1,
+            0
           )
           .accounts({
             proposer: voter1.publicKey,
             governance: governancePDA,
             tokenRegistry: tokenRegistryPDA,
             tokenMint: tokenMint,
+            proposerTokenAccount: voter1TokenAccount,
+            holderSnapshot: null,
             proposal: customDurationProposalPDA,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -390,13 +737,20 @@ describe("community_token_launcher", () => {
             "Invalid Duration Proposal",
             "This proposal has a duration that's too short",
             proposalChoices,
-            invalidDuration
+            invalidDuration,
+            null,
+            false,
+            null,
+            1,
+            0
           )
           .accounts({
             proposer: voter1.publicKey,
             governance: governancePDA,
             tokenRegistry: tokenRegistryPDA,
             tokenMint: tokenMint,
+            proposerTokenAccount: voter1TokenAccount,
+            holderSnapshot: null,
             proposal: invalidDurationProposalPDA,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -447,6 +801,15 @@ describe("community_token_launcher", () => {
         program.programId
       );
 
+      [voterReceiptPDA1] = await PublicKey.findProgramAddress(
+        [
+          Buffer.from("voter_receipt"),
+          votingProposalPDA.toBuffer(),
+          voter1.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
       try {
         // Get voter1 token balance before voting
         const beforeBalance = await provider.connection.getTokenAccountBalance(
@@ -459,6 +822,7 @@ describe("community_token_launcher", () => {
             voter: voter1.publicKey,
             governance: governancePDA,
             proposal: votingProposalPDA,
+            voterReceipt: voterReceiptPDA1,
             choiceEscrow: choiceEscrowPDA1,
             voterTokenAccount: voter1TokenAccount,
             tokenMint: tokenMint,
@@ -548,6 +912,15 @@ describe("community_token_launcher", () => {
         program.programId
       );
 
+      const [voterReceiptPDA2] = await PublicKey.findProgramAddress(
+        [
+          Buffer.from("voter_receipt"),
+          votingProposalPDA.toBuffer(),
+          voter2.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
       try {
         await program.methods
           .lockTokensForChoice(voteAmount2, choiceId2)
@@ -555,6 +928,7 @@ describe("community_token_launcher", () => {
             voter: voter2.publicKey,
             governance: governancePDA,
             proposal: votingProposalPDA,
+            voterReceipt: voterReceiptPDA2,
             choiceEscrow: choiceEscrowPDA2,
             voterTokenAccount: voter2TokenAccount,
             tokenMint: tokenMint,
@@ -601,12 +975,22 @@ describe("community_token_launcher", () => {
           program.programId
         );
 
+        const [voterReceiptPDA3] = await PublicKey.findProgramAddress(
+          [
+            Buffer.from("voter_receipt"),
+            votingProposalPDA.toBuffer(),
+            voter3.publicKey.toBuffer(),
+          ],
+          program.programId
+        );
+
         await program.methods
           .lockTokensForChoice(voteAmount3, choiceId3)
           .accounts({
             voter: voter3.publicKey,
             governance: governancePDA,
             proposal: votingProposalPDA,
+            voterReceipt: voterReceiptPDA3,
             choiceEscrow: choiceEscrowPDA3,
             voterTokenAccount: voter3TokenAccount,
             tokenMint: tokenMint,
@@ -893,5 +1277,2118 @@ describe("community_token_launcher", () => {
         throw error;
       }
     });
+
+    it("Should report whether all escrows have been settled", async () => {
+      try {
+        const isSettled = () =>
+          program.methods
+            .isFullySettled(new BN(votingProposalId))
+            .accounts({
+              governance: governancePDA,
+              proposal: votingProposalPDA,
+            })
+            .view();
+
+        // Voter 2's losing escrow is still outstanding
+        expect(await isSettled()).to.be.false;
+
+        const pdas = findVotePDAs(votingProposalPDA, 1, voter2.publicKey);
+        await program.methods
+          .refundLosingEscrow()
+          .accounts({
+            executor: tokenCreator.publicKey,
+            governance: governancePDA,
+            proposal: votingProposalPDA,
+            choiceEscrow: pdas.choiceEscrow,
+            vaultAuthority: pdas.vaultAuthority,
+            escrowVault: pdas.choiceEscrowVault,
+            voterTokenAccount: voter2TokenAccount,
+            tokenMint: tokenMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([tokenCreator])
+          .rpc();
+
+        expect(await isSettled()).to.be.true;
+      } catch (error) {
+        console.error("Error checking settlement:", error);
+        throw error;
+      }
+    });
+
+    it("Should close a settled escrow and return rent to the voter", async () => {
+      try {
+        const beforeLamports = await provider.connection.getBalance(voter1.publicKey);
+
+        await program.methods
+          .closeChoiceEscrow()
+          .accounts({
+            voter: voter1.publicKey,
+            governance: governancePDA,
+            proposal: votingProposalPDA,
+            voterReceipt: voterReceiptPDA1,
+            choiceEscrow: choiceEscrowPDA1,
+            vaultAuthority: vaultAuthorityPDA1,
+            escrowVault: choiceEscrowVaultPDA1,
+            voterTokenAccount: voter1TokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([voter1])
+          .rpc();
+
+        const afterLamports = await provider.connection.getBalance(voter1.publicKey);
+        expect(afterLamports).to.be.greaterThan(beforeLamports);
+
+        // Escrow, receipt and vault accounts should all be gone
+        expect(await provider.connection.getAccountInfo(choiceEscrowPDA1)).to.be.null;
+        expect(await provider.connection.getAccountInfo(voterReceiptPDA1)).to.be.null;
+        expect(await provider.connection.getAccountInfo(choiceEscrowVaultPDA1)).to.be.null;
+      } catch (error) {
+        console.error("Error closing choice escrow:", error);
+        throw error;
+      }
+    });
+  });
+
+  describe("Governance Validation", () => {
+    it("Should reject percentage-threshold governance for a zero-supply token", async () => {
+      // Create a mint without minting any tokens
+      const emptyMint = await createMint(
+        provider.connection,
+        tokenCreator,
+        tokenCreator.publicKey,
+        null,
+        6
+      );
+
+      const [emptyRegistryPDA] = await PublicKey.findProgramAddress(
+        [Buffer.from("token_registry"), emptyMint.toBuffer()],
+        program.programId
+      );
+
+      const [emptyGovernancePDA] = await PublicKey.findProgramAddress(
+        [Buffer.from("governance"), emptyMint.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeTokenRegistry("Empty Token", "EMPTY")
+        .accounts({
+          authority: tokenCreator.publicKey,
+          tokenMint: emptyMint,
+          tokenRegistry: emptyRegistryPDA,
+          registryIndex: registryIndexPDA,
+          registryIndexPage: await currentRegistryIndexPage(),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      try {
+        await program.methods
+          .initializeGovernance(
+            VOTING_PERIOD,
+            MIN_VOTE_THRESHOLD,
+            PROPOSAL_THRESHOLD,
+            PROPOSAL_THRESHOLD_PERCENTAGE,
+            QUORUM_PERCENTAGE,
+            MAX_LIFETIME_PROPOSALS,
+            TIE_BREAK,
+            EXECUTION_TIMELOCK,
+            PER_CHOICE_THRESHOLD,
+            MIN_HOLD_DURATION,
+            MAX_ACTIVE_PROPOSALS,
+            MIN_VOTE_AMOUNT,
+            MAX_VOTING_PERIOD,
+            "Empty Governance"
+          )
+          .accounts({
+            authority: tokenCreator.publicKey,
+            tokenMint: emptyMint,
+            tokenRegistry: emptyRegistryPDA,
+            governance: emptyGovernancePDA,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([tokenCreator])
+          .rpc();
+
+        expect.fail("Should have rejected governance for a zero-supply token");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidGovernanceSettings");
+      }
+    });
+  });
+
+  describe("Vote Receipts", () => {
+    let receiptProposalPDA: PublicKey;
+    const voteAmount = new BN(500 * Math.pow(10, 6)); // 500 tokens
+
+    before(async () => {
+      receiptProposalPDA = await createProposal(
+        mainCommunity(),
+        voter1,
+        "Receipt Proposal",
+        ["Yes", "No"]
+      );
+    });
+
+    it("Should allow a voter to top up their chosen option", async () => {
+      await lockTokens(mainCommunity(), receiptProposalPDA, voter1, voter1TokenAccount, 0, voteAmount);
+      const pdas = await lockTokens(
+        mainCommunity(),
+        receiptProposalPDA,
+        voter1,
+        voter1TokenAccount,
+        0,
+        voteAmount
+      );
+
+      const escrowAccount = await program.account.choiceEscrow.fetch(pdas.choiceEscrow);
+      expect(escrowAccount.lockedAmount.toNumber()).to.equal(voteAmount.toNumber() * 2);
+
+      const receiptAccount = await program.account.voterReceipt.fetch(pdas.voterReceipt);
+      expect(receiptAccount.voter.toString()).to.equal(voter1.publicKey.toString());
+      expect(receiptAccount.choiceId).to.equal(0);
+
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(receiptProposalPDA);
+      expect(proposalAccount.choiceVoteCounts[0].toNumber()).to.equal(voteAmount.toNumber() * 2);
+    });
+
+    it("Should reject a vote for a different choice", async () => {
+      try {
+        await lockTokens(mainCommunity(), receiptProposalPDA, voter1, voter1TokenAccount, 1, voteAmount);
+        expect.fail("Should have rejected a vote for a second choice");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AlreadyVoted");
+      }
+    });
+  });
+
+  describe("Token Transfer Errors", () => {
+    it("Should report a frozen voter account as AccountFrozen", async () => {
+      const community = await createCommunity("Freezable Token", {
+        freezeAuthority: tokenCreator.publicKey,
+      });
+      const voterTokenAccount = await fundWallet(community, voter1.publicKey, 1000);
+      const proposal = await createProposal(community, voter1, "Frozen Proposal", ["Yes", "No"]);
+
+      await freezeAccount(
+        provider.connection,
+        tokenCreator,
+        voterTokenAccount,
+        community.mint,
+        tokenCreator
+      );
+
+      try {
+        await lockTokens(community, proposal, voter1, voterTokenAccount, 0, new BN(100));
+        expect.fail("Should have rejected a transfer from a frozen account");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AccountFrozen");
+      }
+    });
+  });
+
+  describe("Winner Recipient Override", () => {
+    it("Should distribute a winning escrow to the proposal's override recipient", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Fund Contributor", ["Fund", "Skip"], {
+        winnerRecipientOverride: voter3.publicKey,
+      });
+
+      const voteAmount = new BN(100 * Math.pow(10, 6));
+      const pdas = await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, voteAmount);
+
+      console.log("Waiting for the override proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      await program.methods
+        .executeProposal()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          tokenRegistry: tokenRegistryPDA,
+          governance: governancePDA,
+          proposal: proposal,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const beforeBalance = await provider.connection.getTokenAccountBalance(voter3TokenAccount);
+
+      await program.methods
+        .distributeWinningEscrow()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          governance: governancePDA,
+          proposal: proposal,
+          choiceEscrow: pdas.choiceEscrow,
+          vaultAuthority: pdas.vaultAuthority,
+          escrowVault: pdas.choiceEscrowVault,
+          creatorTokenAccount: voter3TokenAccount,
+          tokenMint: tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const afterBalance = await provider.connection.getTokenAccountBalance(voter3TokenAccount);
+      expect(
+        parseInt(afterBalance.value.amount) - parseInt(beforeBalance.value.amount)
+      ).to.equal(voteAmount.toNumber());
+    });
+  });
+
+  describe("Abstain Choice", () => {
+    it("Should not let abstain win even with the most tokens", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Abstain Proposal", ["Yes", "No"], {
+        hasAbstain: true,
+      });
+
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.choices).to.deep.equal(["Yes", "No", "Abstain"]);
+      expect(proposalAccount.abstainChoice).to.equal(2);
+
+      await lockTokens(mainCommunity(), proposal, voter3, voter3TokenAccount, 2, new BN(300 * Math.pow(10, 6)));
+      await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(100 * Math.pow(10, 6)));
+
+      console.log("Waiting for the abstain proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      await program.methods
+        .executeProposal()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          tokenRegistry: tokenRegistryPDA,
+          governance: governancePDA,
+          proposal: proposal,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const executedProposal = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(executedProposal.status.executed).to.exist;
+      expect(executedProposal.winningChoice).to.equal(0);
+    });
+  });
+
+  describe("Voting Period Validation", () => {
+    it("Should reject a voting duration that overflows the end time", async () => {
+      try {
+        await createProposal(mainCommunity(), voter1, "Overflow Proposal", ["Yes", "No"], {
+          votingDuration: new BN("9223372036854775807"), // i64::MAX
+        });
+        expect.fail("Should have rejected an overflowing voting duration");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("CalculationError");
+      }
+    });
+  });
+
+  describe("Proposal Sizing", () => {
+    it("Should fit a proposal with the maximum number of maximum-length choices", async () => {
+      const choices = Array.from({ length: 10 }, (_, i) => `${i}`.padEnd(50, "x"));
+      const proposal = await createProposal(mainCommunity(), voter1, "Max Choices", choices);
+
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.choices).to.deep.equal(choices);
+    });
+
+    it("Should allocate proposal space for the submitted choices only", async () => {
+      const binary = await createProposal(mainCommunity(), voter1, "Binary Sizing", ["Yes", "No"]);
+      const wide = await createProposal(
+        mainCommunity(),
+        voter1,
+        "Wide Sizing",
+        Array.from({ length: 10 }, (_, i) => `Option ${i}`)
+      );
+
+      const binaryInfo = await provider.connection.getAccountInfo(binary);
+      const wideInfo = await provider.connection.getAccountInfo(wide);
+      expect(binaryInfo.data.length).to.be.lessThan(wideInfo.data.length);
+    });
+
+    it("Should reject over-length titles, descriptions and choices", async () => {
+      const cases: [string, () => Promise<PublicKey>][] = [
+        ["TitleTooLong", () => createProposal(mainCommunity(), voter1, "t".repeat(101), ["Yes", "No"])],
+        [
+          "DescriptionTooLong",
+          () => createProposal(mainCommunity(), voter1, "Long Description", ["Yes", "No"], {
+            description: "d".repeat(501),
+          }),
+        ],
+        ["ChoiceTooLong", () => createProposal(mainCommunity(), voter1, "Long Choice", ["Yes", "n".repeat(51)])],
+      ];
+
+      for (const [code, create] of cases) {
+        try {
+          await create();
+          expect.fail(`Should have failed with ${code}`);
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal(code);
+        }
+      }
+    });
+
+    it("Should reject empty and duplicate choices", async () => {
+      const cases: [string, string[]][] = [
+        ["EmptyChoice", ["Yes", "  "]],
+        ["DuplicateChoice", ["Yes", "No", "Yes"]],
+      ];
+
+      for (const [code, choices] of cases) {
+        try {
+          await createProposal(mainCommunity(), voter1, `Invalid ${code}`, choices);
+          expect.fail(`Should have failed with ${code}`);
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal(code);
+        }
+      }
+    });
+  });
+
+  describe("Supply Quorum", () => {
+    it("Should require turnout to meet the quorum percentage of supply", async () => {
+      // 2,000 tokens of supply with a 50% quorum
+      const community = await createCommunity("Quorum Token", { quorumPercentage: 50 });
+      const voter1Account = await fundWallet(community, voter1.publicKey, 1000);
+      const voter2Account = await fundWallet(community, voter2.publicKey, 1000);
+
+      const lowTurnout = await createProposal(community, voter1, "Low Turnout", ["Yes", "No"]);
+      const highTurnout = await createProposal(community, voter1, "High Turnout", ["Yes", "No"]);
+
+      const supplySnapshot = (await program.account.multiChoiceProposal.fetch(lowTurnout)).supplySnapshot;
+      expect(supplySnapshot.toNumber()).to.equal(2000 * Math.pow(10, 6));
+
+      await lockTokens(community, lowTurnout, voter1, voter1Account, 0, new BN(100 * Math.pow(10, 6)));
+      await lockTokens(community, highTurnout, voter1, voter1Account, 0, new BN(600 * Math.pow(10, 6)));
+      await lockTokens(community, highTurnout, voter2, voter2Account, 1, new BN(600 * Math.pow(10, 6)));
+
+      console.log("Waiting for the quorum proposals voting period to end...");
+      await sleep(65 * 1000);
+
+      const execute = (proposal: PublicKey) =>
+        program.methods
+          .executeProposal()
+          .accounts({
+            executor: tokenCreator.publicKey,
+            tokenRegistry: community.registry,
+            governance: community.governance,
+            proposal: proposal,
+          })
+          .signers([tokenCreator])
+          .rpc();
+
+      try {
+        await execute(lowTurnout);
+        expect.fail("Should have rejected execution below quorum");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("QuorumNotMet");
+      }
+
+      await execute(highTurnout);
+      const executedProposal = await program.account.multiChoiceProposal.fetch(highTurnout);
+      expect(executedProposal.status.executed).to.exist;
+    });
+  });
+
+  describe("Vote Reduction", () => {
+    it("Should return part of a vote while voting is open", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Reducible Proposal", ["Yes", "No"]);
+      const pdas = await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(1000));
+
+      await program.methods
+        .reduceVote(new BN(400))
+        .accounts({
+          voter: voter2.publicKey,
+          governance: governancePDA,
+          proposal: proposal,
+          choiceEscrow: pdas.choiceEscrow,
+          vaultAuthority: pdas.vaultAuthority,
+          escrowVault: pdas.choiceEscrowVault,
+          voterTokenAccount: voter2TokenAccount,
+          tokenMint: tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([voter2])
+        .rpc();
+
+      const vaultBalance = await provider.connection.getTokenAccountBalance(pdas.choiceEscrowVault);
+      expect(parseInt(vaultBalance.value.amount)).to.equal(600);
+
+      const escrowAccount = await program.account.choiceEscrow.fetch(pdas.choiceEscrow);
+      expect(escrowAccount.lockedAmount.toNumber()).to.equal(600);
+
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.choiceVoteCounts[0].toNumber()).to.equal(600);
+    });
+
+    it("Should count an emptied escrow as closed only once", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Emptied Escrow", ["Yes", "No"]);
+      const emptied = await lockTokens(mainCommunity(), proposal, voter1, voter1TokenAccount, 0, new BN(1000));
+      const loser = await lockTokens(mainCommunity(), proposal, voter3, voter3TokenAccount, 0, new BN(1000));
+      await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 1, new BN(100 * Math.pow(10, 6)));
+
+      const reduce = (amount: BN) =>
+        program.methods
+          .reduceVote(amount)
+          .accounts({
+            voter: voter1.publicKey,
+            governance: governancePDA,
+            proposal: proposal,
+            choiceEscrow: emptied.choiceEscrow,
+            vaultAuthority: emptied.vaultAuthority,
+            escrowVault: emptied.choiceEscrowVault,
+            voterTokenAccount: voter1TokenAccount,
+            tokenMint: tokenMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([voter1])
+          .rpc();
+
+      await reduce(new BN(1000));
+      let proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.openEscrowCount.toNumber()).to.equal(2);
+
+      try {
+        await reduce(new BN(0));
+        expect.fail("Should have rejected a zero reduction");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ZeroReduction");
+      }
+      proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.openEscrowCount.toNumber()).to.equal(2);
+
+      console.log("Waiting for the emptied escrow proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      await program.methods
+        .executeProposal()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          tokenRegistry: tokenRegistryPDA,
+          governance: governancePDA,
+          proposal: proposal,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      // Other escrows still settle normally
+      await program.methods
+        .refundLosingEscrow()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          governance: governancePDA,
+          proposal: proposal,
+          choiceEscrow: loser.choiceEscrow,
+          vaultAuthority: loser.vaultAuthority,
+          escrowVault: loser.choiceEscrowVault,
+          voterTokenAccount: voter3TokenAccount,
+          tokenMint: tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.openEscrowCount.toNumber()).to.equal(1);
+    });
+  });
+
+  describe("Proposal Start Delay", () => {
+    it("Should only accept votes once the start time is reached", async () => {
+      const slot = await provider.connection.getSlot();
+      const chainTime = await provider.connection.getBlockTime(slot);
+      const startAt = new BN(chainTime + 10);
+
+      const proposal = await createProposal(mainCommunity(), voter1, "Delayed Proposal", ["Yes", "No"], {
+        startAt,
+      });
+
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.startAt.toString()).to.equal(startAt.toString());
+      expect(proposalAccount.endsAt.toString()).to.equal(startAt.add(VOTING_PERIOD).toString());
+
+      try {
+        await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(100));
+        expect.fail("Should have rejected a vote before the start time");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("VotingNotStarted");
+      }
+
+      await sleep(12 * 1000);
+
+      await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(100));
+      const votedProposal = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(votedProposal.choiceVoteCounts[0].toNumber()).to.equal(100);
+    });
+  });
+
+  describe("Batch Distribution", () => {
+    it("Should distribute three winning escrows in a single call", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Batch Proposal", ["Yes", "No"]);
+      const voteAmount = new BN(50 * Math.pow(10, 6));
+
+      const voters: [Keypair, PublicKey][] = [
+        [voter1, voter1TokenAccount],
+        [voter2, voter2TokenAccount],
+        [voter3, voter3TokenAccount],
+      ];
+      const escrows = [];
+      for (const [voter, tokenAccount] of voters) {
+        escrows.push(await lockTokens(mainCommunity(), proposal, voter, tokenAccount, 0, voteAmount));
+      }
+
+      console.log("Waiting for the batch proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      await program.methods
+        .executeProposal()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          tokenRegistry: tokenRegistryPDA,
+          governance: governancePDA,
+          proposal: proposal,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const distributeBatch = (vaults: PublicKey[]) =>
+        program.methods
+          .distributeWinningEscrowsBatch()
+          .accounts({
+            executor: tokenCreator.publicKey,
+            governance: governancePDA,
+            proposal: proposal,
+            creatorTokenAccount: creatorTokenAccount,
+            tokenMint: tokenMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .remainingAccounts(
+            escrows.flatMap((pdas, i) => [
+              { pubkey: pdas.choiceEscrow, isSigner: false, isWritable: true },
+              { pubkey: vaults[i], isSigner: false, isWritable: true },
+              { pubkey: pdas.vaultAuthority, isSigner: false, isWritable: false },
+            ])
+          )
+          .signers([tokenCreator])
+          .rpc();
+
+      // A funded token account owned by the vault authority is not the escrow's vault
+      const foreignVault = await createAccount(
+        provider.connection,
+        tokenCreator,
+        tokenMint,
+        escrows[0].vaultAuthority,
+        Keypair.generate()
+      );
+      await mintTo(provider.connection, tokenCreator, tokenMint, foreignVault, tokenCreator, voteAmount.toNumber());
+      try {
+        await distributeBatch([foreignVault, ...escrows.slice(1).map((pdas) => pdas.choiceEscrowVault)]);
+        expect.fail("Should have rejected a vault that is not the escrow's own");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidBatchAccounts");
+      }
+      const untouchedEscrow = await program.account.choiceEscrow.fetch(escrows[0].choiceEscrow);
+      expect(untouchedEscrow.distributed).to.be.false;
+
+      const beforeBalance = await provider.connection.getTokenAccountBalance(creatorTokenAccount);
+
+      await distributeBatch(escrows.map((pdas) => pdas.choiceEscrowVault));
+
+      const afterBalance = await provider.connection.getTokenAccountBalance(creatorTokenAccount);
+      expect(
+        parseInt(afterBalance.value.amount) - parseInt(beforeBalance.value.amount)
+      ).to.equal(voteAmount.toNumber() * 3);
+
+      const settledProposal = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(settledProposal.openEscrowCount.toNumber()).to.equal(0);
+    });
+  });
+
+  describe("Execution Status", () => {
+    it("Should report why a proposal can or cannot be executed", async () => {
+      const getStatus = async (proposal: PublicKey) => {
+        const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+        return program.methods
+          .getExecutionStatus(proposalAccount.id)
+          .accounts({
+            governance: governancePDA,
+            proposal: proposal,
+          })
+          .view();
+      };
+
+      const emptyProposal = await createProposal(mainCommunity(), voter1, "Unvoted Proposal", ["Yes", "No"]);
+      const votedProposal = await createProposal(mainCommunity(), voter1, "Voted Proposal", ["Yes", "No"]);
+      await lockTokens(mainCommunity(), votedProposal, voter2, voter2TokenAccount, 0, new BN(100 * Math.pow(10, 6)));
+
+      const openStatus = await getStatus(votedProposal);
+      expect(openStatus.votingEnded).to.be.false;
+      expect(openStatus.executable).to.be.false;
+      expect(openStatus.blockingReason.votingNotEnded).to.exist;
+
+      console.log("Waiting for the status proposals voting period to end...");
+      await sleep(65 * 1000);
+
+      const belowThresholdStatus = await getStatus(emptyProposal);
+      expect(belowThresholdStatus.votingEnded).to.be.true;
+      expect(belowThresholdStatus.thresholdMet).to.be.false;
+      expect(belowThresholdStatus.executable).to.be.false;
+      expect(belowThresholdStatus.blockingReason.voteThresholdNotMet).to.exist;
+
+      const readyStatus = await getStatus(votedProposal);
+      expect(readyStatus.votingEnded).to.be.true;
+      expect(readyStatus.thresholdMet).to.be.true;
+      expect(readyStatus.quorumMet).to.be.true;
+      expect(readyStatus.alreadyExecuted).to.be.false;
+      expect(readyStatus.executable).to.be.true;
+      expect(readyStatus.blockingReason).to.be.null;
+    });
+  });
+
+  describe("Proposal Threshold", () => {
+    it("Should enforce the percentage threshold on a very high supply token", async () => {
+      // 2e17 base units of supply, so supply * 100 exceeds u64::MAX
+      const community = await createCommunity("High Supply Token", {
+        proposalThresholdPercentage: 30,
+        holders: [
+          [voter1.publicKey, 150_000_000_000], // 75% of supply
+          [voter2.publicKey, 50_000_000_000], // 25% of supply
+        ],
+      });
+
+      const mintSupply = (await provider.connection.getTokenSupply(community.mint)).value.amount;
+      expect(mintSupply).to.equal("200000000000000000");
+
+      try {
+        await createProposal(community, voter2, "Under Threshold", ["Yes", "No"]);
+        expect.fail("Should have rejected a proposer below the percentage threshold");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InsufficientTokensToPropose");
+      }
+
+      const proposal = await createProposal(community, voter1, "Over Threshold", ["Yes", "No"]);
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.supplySnapshot.toString()).to.equal(mintSupply);
+    });
+  });
+
+  describe("Lifetime Proposal Limit", () => {
+    it("Should reject proposals beyond the lifetime cap", async () => {
+      const community = await createCommunity("Capped Token", {
+        maxLifetimeProposals: 2,
+        holders: [[voter1.publicKey, 1000]],
+      });
+
+      await createProposal(community, voter1, "First Proposal", ["Yes", "No"]);
+      await createProposal(community, voter1, "Second Proposal", ["Yes", "No"]);
+
+      try {
+        await createProposal(community, voter1, "Third Proposal", ["Yes", "No"]);
+        expect.fail("Should have rejected a proposal beyond the lifetime cap");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ProposalLimitReached");
+      }
+    });
+  });
+
+  describe("Tie Breaking", () => {
+    it("Should resolve an exact tie according to the governance's tie-break mode", async () => {
+      const tieVote = new BN(100 * Math.pow(10, 6));
+      const modes = [
+        { name: "Lowest Index Tie", tieBreak: { lowestIndex: {} } },
+        { name: "Reject Tie", tieBreak: { reject: {} } },
+        { name: "Extend Tie", tieBreak: { extendVoting: {} } },
+      ];
+
+      const tiedProposals = [];
+      for (const mode of modes) {
+        const community = await createCommunity(mode.name, {
+          tieBreak: mode.tieBreak,
+          holders: [
+            [voter1.publicKey, 1000],
+            [voter2.publicKey, 1000],
+          ],
+        });
+        const proposal = await createProposal(community, voter1, mode.name, ["Yes", "No"]);
+        const voter1Account = getAssociatedTokenAddressSync(community.mint, voter1.publicKey);
+        const voter2Account = getAssociatedTokenAddressSync(community.mint, voter2.publicKey);
+
+        await lockTokens(community, proposal, voter1, voter1Account, 0, tieVote);
+        await lockTokens(community, proposal, voter2, voter2Account, 1, tieVote);
+        tiedProposals.push({ community, proposal });
+      }
+
+      console.log("Waiting for the tied proposals voting period to end...");
+      await sleep(65 * 1000);
+
+      const results = [];
+      for (const { community, proposal } of tiedProposals) {
+        const before = await program.account.multiChoiceProposal.fetch(proposal);
+
+        await program.methods
+          .executeProposal()
+          .accounts({
+            executor: tokenCreator.publicKey,
+            tokenRegistry: community.registry,
+            governance: community.governance,
+            proposal: proposal,
+          })
+          .signers([tokenCreator])
+          .rpc();
+
+        results.push({ before, after: await program.account.multiChoiceProposal.fetch(proposal) });
+      }
+
+      const [lowestIndex, reject, extend] = results;
+
+      expect(lowestIndex.after.status.executed).to.exist;
+      expect(lowestIndex.after.winningChoice).to.equal(0);
+
+      expect(reject.after.status.rejected).to.exist;
+      expect(reject.after.winningChoice).to.be.null;
+
+      expect(extend.after.status.active).to.exist;
+      expect(extend.after.endsAt.toNumber()).to.equal(extend.before.endsAt.toNumber() + 86400);
+    });
+  });
+
+  describe("Failed Proposals", () => {
+    it("Should reject an under-threshold proposal and refund its voters", async () => {
+      const community = await createCommunity("Failed Token", {
+        holders: [[voter1.publicKey, 1000]],
+      });
+      const voter1Account = getAssociatedTokenAddressSync(community.mint, voter1.publicKey);
+      const proposal = await createProposal(community, voter1, "Failed Proposal", ["Yes", "No"]);
+
+      // Below MIN_VOTE_THRESHOLD
+      const pdas = await lockTokens(community, proposal, voter1, voter1Account, 0, new BN(50));
+      const lockedBalance = await provider.connection.getTokenAccountBalance(voter1Account);
+
+      const finalize = () =>
+        program.methods
+          .finalizeFailedProposal()
+          .accounts({
+            caller: voter2.publicKey,
+            governance: community.governance,
+            proposal: proposal,
+          })
+          .signers([voter2])
+          .rpc();
+
+      try {
+        await finalize();
+        expect.fail("Should not finalize while voting is open");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("VotingNotEnded");
+      }
+
+      console.log("Waiting for the failed proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      await finalize();
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.status.rejected).to.exist;
+      expect(proposalAccount.winningChoice).to.be.null;
+
+      await program.methods
+        .refundLosingEscrow()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          governance: community.governance,
+          proposal: proposal,
+          choiceEscrow: pdas.choiceEscrow,
+          vaultAuthority: pdas.vaultAuthority,
+          escrowVault: pdas.choiceEscrowVault,
+          voterTokenAccount: voter1Account,
+          tokenMint: community.mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const refundedBalance = await provider.connection.getTokenAccountBalance(voter1Account);
+      expect(parseInt(refundedBalance.value.amount)).to.equal(
+        parseInt(lockedBalance.value.amount) + 50
+      );
+    });
+  });
+
+  describe("Vote Delegation", () => {
+    it("Should let a delegate vote on the delegator's behalf until revoked", async () => {
+      const community = await createCommunity("Delegated Token", {
+        holders: [[voter1.publicKey, 1000]],
+      });
+      const delegatorAccount = getAssociatedTokenAddressSync(community.mint, voter1.publicKey);
+      const proposal = await createProposal(community, voter1, "Delegated Proposal", ["Yes", "No"]);
+      const [delegation] = PublicKey.findProgramAddressSync(
+        [Buffer.from("delegation"), community.mint.toBuffer(), voter1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .setDelegate(voter2.publicKey)
+        .accounts({
+          delegator: voter1.publicKey,
+          tokenMint: community.mint,
+          delegation: delegation,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter1])
+        .rpc();
+
+      // The delegate also needs SPL approval to move the delegator's tokens
+      await approve(provider.connection, voter1, delegatorAccount, voter2.publicKey, voter1, 1000);
+
+      const voteAsDelegate = (choiceId: number, amount: BN) => {
+        const pdas = findVotePDAs(proposal, choiceId, voter1.publicKey);
+        return program.methods
+          .lockTokensForChoiceAsDelegate(amount, choiceId)
+          .accounts({
+            delegate: voter2.publicKey,
+            delegator: voter1.publicKey,
+            delegation: delegation,
+            governance: community.governance,
+            proposal: proposal,
+            voterReceipt: pdas.voterReceipt,
+            choiceEscrow: pdas.choiceEscrow,
+            voterTokenAccount: delegatorAccount,
+            tokenMint: community.mint,
+            vaultAuthority: pdas.vaultAuthority,
+            choiceEscrowVault: pdas.choiceEscrowVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            rent: SYSVAR_RENT_PUBKEY,
+          })
+          .signers([voter2])
+          .rpc();
+      };
+
+      await voteAsDelegate(0, new BN(600));
+
+      const pdas = findVotePDAs(proposal, 0, voter1.publicKey);
+      const escrowAccount = await program.account.choiceEscrow.fetch(pdas.choiceEscrow);
+      expect(escrowAccount.voter.toString()).to.equal(voter1.publicKey.toString());
+      expect(escrowAccount.lockedAmount.toNumber()).to.equal(600);
+
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.choiceVoteCounts[0].toNumber()).to.equal(600);
+
+      await program.methods
+        .clearDelegate()
+        .accounts({
+          delegator: voter1.publicKey,
+          tokenMint: community.mint,
+          delegation: delegation,
+        })
+        .signers([voter1])
+        .rpc();
+
+      try {
+        await voteAsDelegate(0, new BN(100));
+        expect.fail("Should have rejected a revoked delegate");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("NoActiveDelegation");
+      }
+    });
+  });
+
+  describe("Execution Timelock", () => {
+    it("Should block distribution until the timelock has elapsed", async () => {
+      const community = await createCommunity("Timelocked Token", {
+        executionTimelock: 30,
+        holders: [[voter1.publicKey, 1000]],
+      });
+      const voter1Account = getAssociatedTokenAddressSync(community.mint, voter1.publicKey);
+      const creatorAccount = await fundWallet(community, tokenCreator.publicKey, 0);
+      const proposal = await createProposal(community, voter1, "Timelocked Proposal", ["Yes", "No"]);
+
+      const voteAmount = new BN(100 * Math.pow(10, 6));
+      const pdas = await lockTokens(community, proposal, voter1, voter1Account, 0, voteAmount);
+
+      console.log("Waiting for the timelocked proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      await program.methods
+        .executeProposal()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          tokenRegistry: community.registry,
+          governance: community.governance,
+          proposal: proposal,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.executedAt.toNumber()).to.be.greaterThan(0);
+
+      const distribute = () =>
+        program.methods
+          .distributeWinningEscrow()
+          .accounts({
+            executor: tokenCreator.publicKey,
+            governance: community.governance,
+            proposal: proposal,
+            choiceEscrow: pdas.choiceEscrow,
+            vaultAuthority: pdas.vaultAuthority,
+            escrowVault: pdas.choiceEscrowVault,
+            creatorTokenAccount: creatorAccount,
+            tokenMint: community.mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([tokenCreator])
+          .rpc();
+
+      try {
+        await distribute();
+        expect.fail("Should not distribute during the timelock");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("TimelockActive");
+      }
+
+      console.log("Waiting for the execution timelock to elapse...");
+      await sleep(35 * 1000);
+
+      await distribute();
+      const creatorBalance = await provider.connection.getTokenAccountBalance(creatorAccount);
+      expect(parseInt(creatorBalance.value.amount)).to.equal(voteAmount.toNumber());
+    });
+  });
+
+  describe("Per-Choice Threshold", () => {
+    it("Should reject a plurality winner below the per-choice floor", async () => {
+      const community = await createCommunity("Floor Token", {
+        perChoiceThreshold: new BN(500 * Math.pow(10, 6)),
+        holders: [
+          [voter1.publicKey, 1000],
+          [voter2.publicKey, 1000],
+        ],
+      });
+      const voter1Account = getAssociatedTokenAddressSync(community.mint, voter1.publicKey);
+      const voter2Account = getAssociatedTokenAddressSync(community.mint, voter2.publicKey);
+      const proposal = await createProposal(community, voter1, "Weak Winner", ["Yes", "No"]);
+
+      // 500 tokens of turnout, but the leading choice only has 300
+      await lockTokens(community, proposal, voter1, voter1Account, 0, new BN(300 * Math.pow(10, 6)));
+      await lockTokens(community, proposal, voter2, voter2Account, 1, new BN(200 * Math.pow(10, 6)));
+
+      console.log("Waiting for the per-choice threshold proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      await program.methods
+        .executeProposal()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          tokenRegistry: community.registry,
+          governance: community.governance,
+          proposal: proposal,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.status.rejected).to.exist;
+      expect(proposalAccount.winningChoice).to.be.null;
+    });
+  });
+
+  describe("Governance Activation", () => {
+    it("Should block proposal creation while governance is inactive", async () => {
+      const community = await createCommunity("Paused Token", {
+        holders: [[voter1.publicKey, 1000]],
+      });
+
+      const setActive = (active: boolean) =>
+        program.methods
+          .setGovernanceActive(active)
+          .accounts({
+            authority: tokenCreator.publicKey,
+            governance: community.governance,
+          })
+          .signers([tokenCreator])
+          .rpc();
+
+      try {
+        await program.methods
+          .setGovernanceActive(false)
+          .accounts({
+            authority: voter1.publicKey,
+            governance: community.governance,
+          })
+          .signers([voter1])
+          .rpc();
+        expect.fail("Only the governance authority should toggle activation");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("Unauthorized");
+      }
+
+      await setActive(false);
+      expect((await program.account.governance.fetch(community.governance)).isActive).to.be.false;
+
+      try {
+        await createProposal(community, voter1, "Paused Proposal", ["Yes", "No"]);
+        expect.fail("Should not create proposals while inactive");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("GovernanceInactive");
+      }
+
+      await setActive(true);
+      const proposal = await createProposal(community, voter1, "Resumed Proposal", ["Yes", "No"]);
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.status.active).to.exist;
+    });
+  });
+
+  describe("Vote Changes", () => {
+    it("Should move a vote from one choice to another", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Changeable Proposal", ["A", "B"]);
+      const oldPdas = await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(1000));
+      const newPdas = findVotePDAs(proposal, 1, voter2.publicKey);
+
+      // Tokens sent straight to the vault must not block closing it
+      await transfer(provider.connection, voter1, voter1TokenAccount, oldPdas.choiceEscrowVault, voter1, 7);
+      const beforeBalance = await provider.connection.getTokenAccountBalance(voter2TokenAccount);
+
+      await program.methods
+        .changeVote(1)
+        .accounts({
+          voter: voter2.publicKey,
+          governance: governancePDA,
+          proposal: proposal,
+          voterReceipt: oldPdas.voterReceipt,
+          oldChoiceEscrow: oldPdas.choiceEscrow,
+          oldVaultAuthority: oldPdas.vaultAuthority,
+          oldEscrowVault: oldPdas.choiceEscrowVault,
+          newChoiceEscrow: newPdas.choiceEscrow,
+          newVaultAuthority: newPdas.vaultAuthority,
+          newEscrowVault: newPdas.choiceEscrowVault,
+          voterTokenAccount: voter2TokenAccount,
+          tokenMint: tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([voter2])
+        .rpc();
+
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.choiceVoteCounts[0].toNumber()).to.equal(0);
+      expect(proposalAccount.choiceVoteCounts[1].toNumber()).to.equal(1000);
+      expect(proposalAccount.openEscrowCount.toNumber()).to.equal(1);
+
+      const newEscrow = await program.account.choiceEscrow.fetch(newPdas.choiceEscrow);
+      expect(newEscrow.choiceId).to.equal(1);
+      expect(newEscrow.lockedAmount.toNumber()).to.equal(1000);
+
+      const vaultBalance = await provider.connection.getTokenAccountBalance(newPdas.choiceEscrowVault);
+      expect(parseInt(vaultBalance.value.amount)).to.equal(1000);
+
+      expect(await program.account.choiceEscrow.fetchNullable(oldPdas.choiceEscrow)).to.be.null;
+      expect(await provider.connection.getAccountInfo(oldPdas.choiceEscrowVault)).to.be.null;
+
+      const receipt = await program.account.voterReceipt.fetch(oldPdas.voterReceipt);
+      expect(receipt.choiceId).to.equal(1);
+
+      const afterBalance = await provider.connection.getTokenAccountBalance(voter2TokenAccount);
+      expect(parseInt(afterBalance.value.amount) - parseInt(beforeBalance.value.amount)).to.equal(7);
+    });
+  });
+
+  describe("Proposal Results", () => {
+    it("Should return the per-choice tallies of an active proposal", async () => {
+      const governanceAccount = await program.account.governance.fetch(governancePDA);
+      const proposalId = governanceAccount.proposalCount;
+      const proposal = await createProposal(mainCommunity(), voter1, "Results Proposal", ["A", "B", "C"]);
+
+      await lockTokens(mainCommunity(), proposal, voter1, voter1TokenAccount, 0, new BN(700));
+      await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 2, new BN(300));
+
+      const results = await program.methods
+        .getProposalResults(proposalId)
+        .accounts({
+          governance: governancePDA,
+          proposal: proposal,
+        })
+        .view();
+
+      expect(results.id.toNumber()).to.equal(proposalId.toNumber());
+      expect(results.status.active).to.exist;
+      expect(results.winningChoice).to.be.null;
+      expect(Array.from(results.winningChoices)).to.deep.equal([]);
+      expect(results.totalVotes.toNumber()).to.equal(1000);
+      expect(results.choiceVoteCounts.map((count) => count.toNumber())).to.deep.equal([700, 0, 300]);
+    });
+  });
+
+  describe("Double Distribution", () => {
+    it("Should refuse to distribute the same escrow twice", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Distribute Once", ["Yes", "No"]);
+      const pdas = await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(1000));
+
+      console.log("Waiting for the double distribution proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      await program.methods
+        .executeProposal()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          tokenRegistry: tokenRegistryPDA,
+          governance: governancePDA,
+          proposal: proposal,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const distribute = () =>
+        program.methods
+          .distributeWinningEscrow()
+          .accounts({
+            executor: tokenCreator.publicKey,
+            governance: governancePDA,
+            proposal: proposal,
+            choiceEscrow: pdas.choiceEscrow,
+            vaultAuthority: pdas.vaultAuthority,
+            escrowVault: pdas.choiceEscrowVault,
+            creatorTokenAccount: creatorTokenAccount,
+            tokenMint: tokenMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([tokenCreator])
+          .rpc();
+
+      await distribute();
+      const escrowAccount = await program.account.choiceEscrow.fetch(pdas.choiceEscrow);
+      expect(escrowAccount.distributed).to.be.true;
+
+      try {
+        await distribute();
+        expect.fail("Should not distribute an escrow twice");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AlreadyDistributed");
+      }
+    });
+  });
+
+  describe("Multiple Winners", () => {
+    it("Should let the top two choices win and refund the third", async () => {
+      const community = await createCommunity("Grant Token", {
+        holders: [
+          [voter1.publicKey, 1000],
+          [voter2.publicKey, 1000],
+          [voter3.publicKey, 1000],
+        ],
+      });
+      const accounts = [voter1, voter2, voter3].map((voter) =>
+        getAssociatedTokenAddressSync(community.mint, voter.publicKey)
+      );
+      const creatorAccount = await fundWallet(community, tokenCreator.publicKey, 0);
+      const proposal = await createProposal(community, voter1, "Grant Round", ["Alpha", "Beta", "Gamma"], {
+        winnersCount: 2,
+      });
+
+      const alpha = await lockTokens(community, proposal, voter1, accounts[0], 0, new BN(100 * Math.pow(10, 6)));
+      const beta = await lockTokens(community, proposal, voter2, accounts[1], 1, new BN(300 * Math.pow(10, 6)));
+      const gamma = await lockTokens(community, proposal, voter3, accounts[2], 2, new BN(200 * Math.pow(10, 6)));
+
+      console.log("Waiting for the multiple winner proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      await program.methods
+        .executeProposal()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          tokenRegistry: community.registry,
+          governance: community.governance,
+          proposal: proposal,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.winningChoice).to.equal(1);
+      expect(Array.from(proposalAccount.winningChoices)).to.deep.equal([1, 2]);
+
+      const results = await program.methods
+        .getProposalResults(proposalAccount.id)
+        .accounts({
+          governance: community.governance,
+          proposal: proposal,
+        })
+        .view();
+      expect(Array.from(results.winningChoices)).to.deep.equal([1, 2]);
+
+      const distribute = (pdas: ReturnType<typeof findVotePDAs>) =>
+        program.methods
+          .distributeWinningEscrow()
+          .accounts({
+            executor: tokenCreator.publicKey,
+            governance: community.governance,
+            proposal: proposal,
+            choiceEscrow: pdas.choiceEscrow,
+            vaultAuthority: pdas.vaultAuthority,
+            escrowVault: pdas.choiceEscrowVault,
+            creatorTokenAccount: creatorAccount,
+            tokenMint: community.mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([tokenCreator])
+          .rpc();
+
+      await distribute(beta);
+      await distribute(gamma);
+      const creatorBalance = await provider.connection.getTokenAccountBalance(creatorAccount);
+      expect(parseInt(creatorBalance.value.amount)).to.equal(500 * Math.pow(10, 6));
+
+      try {
+        await distribute(alpha);
+        expect.fail("Should not distribute an escrow outside the winning set");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("NotWinningEscrow");
+      }
+
+      await program.methods
+        .refundLosingEscrow()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          governance: community.governance,
+          proposal: proposal,
+          choiceEscrow: alpha.choiceEscrow,
+          vaultAuthority: alpha.vaultAuthority,
+          escrowVault: alpha.choiceEscrowVault,
+          voterTokenAccount: accounts[0],
+          tokenMint: community.mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const refundedBalance = await provider.connection.getTokenAccountBalance(accounts[0]);
+      expect(parseInt(refundedBalance.value.amount)).to.equal(1000 * Math.pow(10, 6));
+    });
+  });
+
+  describe("Holding Snapshots", () => {
+    it("Should only accept holding snapshots older than the minimum hold duration", async () => {
+      const community = await createCommunity("Held Token", {
+        minHoldDuration: 10,
+        holders: [[voter1.publicKey, 1000]],
+      });
+      const voter1Account = getAssociatedTokenAddressSync(community.mint, voter1.publicKey);
+      const [holderSnapshot] = PublicKey.findProgramAddressSync(
+        [Buffer.from("holder_snapshot"), community.mint.toBuffer(), voter1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await createProposal(community, voter1, "No Snapshot", ["Yes", "No"]);
+        expect.fail("Should require a holding snapshot");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("HoldingSnapshotRequired");
+      }
+
+      await program.methods
+        .recordHolding()
+        .accounts({
+          holder: voter1.publicKey,
+          tokenMint: community.mint,
+          holderTokenAccount: voter1Account,
+          holderSnapshot: holderSnapshot,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter1])
+        .rpc();
+
+      const snapshotAccount = await program.account.holderSnapshot.fetch(holderSnapshot);
+      expect(snapshotAccount.amount.toNumber()).to.equal(1000 * Math.pow(10, 6));
+
+      try {
+        await createProposal(community, voter1, "Fresh Snapshot", ["Yes", "No"], { holderSnapshot });
+        expect.fail("Should reject a snapshot younger than the hold duration");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("HoldingTooRecent");
+      }
+
+      console.log("Waiting for the holding snapshot to age...");
+      await sleep(12 * 1000);
+
+      const proposal = await createProposal(community, voter1, "Aged Snapshot", ["Yes", "No"], { holderSnapshot });
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.status.active).to.exist;
+    });
+  });
+
+  describe("Escrow Dust", () => {
+    it("Should sweep a small leftover balance from a settled escrow vault", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Dusty Proposal", ["Yes", "No"]);
+      const pdas = await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(1000));
+
+      console.log("Waiting for the dust proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      await program.methods
+        .executeProposal()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          tokenRegistry: tokenRegistryPDA,
+          governance: governancePDA,
+          proposal: proposal,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      await program.methods
+        .distributeWinningEscrow()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          governance: governancePDA,
+          proposal: proposal,
+          choiceEscrow: pdas.choiceEscrow,
+          vaultAuthority: pdas.vaultAuthority,
+          escrowVault: pdas.choiceEscrowVault,
+          creatorTokenAccount: creatorTokenAccount,
+          tokenMint: tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const sweep = () =>
+        program.methods
+          .sweepEscrowDust()
+          .accounts({
+            executor: tokenCreator.publicKey,
+            governance: governancePDA,
+            proposal: proposal,
+            choiceEscrow: pdas.choiceEscrow,
+            vaultAuthority: pdas.vaultAuthority,
+            escrowVault: pdas.choiceEscrowVault,
+            creatorTokenAccount: creatorTokenAccount,
+            tokenMint: tokenMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([tokenCreator])
+          .rpc();
+
+      // Leave a few base units behind in the settled vault
+      await transfer(provider.connection, voter1, voter1TokenAccount, pdas.choiceEscrowVault, voter1, 3);
+      const beforeBalance = await provider.connection.getTokenAccountBalance(creatorTokenAccount);
+
+      await sweep();
+
+      const afterBalance = await provider.connection.getTokenAccountBalance(creatorTokenAccount);
+      expect(parseInt(afterBalance.value.amount) - parseInt(beforeBalance.value.amount)).to.equal(3);
+      const vaultBalance = await provider.connection.getTokenAccountBalance(pdas.choiceEscrowVault);
+      expect(parseInt(vaultBalance.value.amount)).to.equal(0);
+
+      // Anything beyond dust is not sweepable
+      await transfer(provider.connection, voter1, voter1TokenAccount, pdas.choiceEscrowVault, voter1, 100);
+      try {
+        await sweep();
+        expect.fail("Should not sweep more than the dust threshold");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("DustAboveThreshold");
+      }
+
+      // A larger donation still cannot block closing the settled escrow; it goes to the voter
+      const voterBefore = await provider.connection.getTokenAccountBalance(voter2TokenAccount);
+      await program.methods
+        .closeChoiceEscrow()
+        .accounts({
+          voter: voter2.publicKey,
+          governance: governancePDA,
+          proposal: proposal,
+          voterReceipt: pdas.voterReceipt,
+          choiceEscrow: pdas.choiceEscrow,
+          vaultAuthority: pdas.vaultAuthority,
+          escrowVault: pdas.choiceEscrowVault,
+          voterTokenAccount: voter2TokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([voter2])
+        .rpc();
+
+      const voterAfter = await provider.connection.getTokenAccountBalance(voter2TokenAccount);
+      expect(parseInt(voterAfter.value.amount) - parseInt(voterBefore.value.amount)).to.equal(100);
+      expect(await provider.connection.getAccountInfo(pdas.choiceEscrowVault)).to.be.null;
+    });
+
+    it("Should sweep dust from an escrow emptied by a vote reduction", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Withdrawn Dust", ["Yes", "No"]);
+      const pdas = await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(1000));
+
+      await program.methods
+        .reduceVote(new BN(1000))
+        .accounts({
+          voter: voter2.publicKey,
+          governance: governancePDA,
+          proposal: proposal,
+          choiceEscrow: pdas.choiceEscrow,
+          vaultAuthority: pdas.vaultAuthority,
+          escrowVault: pdas.choiceEscrowVault,
+          voterTokenAccount: voter2TokenAccount,
+          tokenMint: tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([voter2])
+        .rpc();
+
+      await transfer(provider.connection, voter1, voter1TokenAccount, pdas.choiceEscrowVault, voter1, 3);
+
+      await program.methods
+        .vetoProposal()
+        .accounts({
+          authority: tokenCreator.publicKey,
+          governance: governancePDA,
+          proposal: proposal,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      await program.methods
+        .sweepEscrowDust()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          governance: governancePDA,
+          proposal: proposal,
+          choiceEscrow: pdas.choiceEscrow,
+          vaultAuthority: pdas.vaultAuthority,
+          escrowVault: pdas.choiceEscrowVault,
+          creatorTokenAccount: creatorTokenAccount,
+          tokenMint: tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      // With the vault empty the escrow can now be closed
+      await program.methods
+        .closeChoiceEscrow()
+        .accounts({
+          voter: voter2.publicKey,
+          governance: governancePDA,
+          proposal: proposal,
+          voterReceipt: pdas.voterReceipt,
+          choiceEscrow: pdas.choiceEscrow,
+          vaultAuthority: pdas.vaultAuthority,
+          escrowVault: pdas.choiceEscrowVault,
+          voterTokenAccount: voter2TokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([voter2])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(pdas.choiceEscrowVault)).to.be.null;
+    });
+  });
+
+  describe("Proposal Veto", () => {
+    it("Should let the governance authority veto an active proposal and refund its voters", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Malicious Proposal", ["Drain", "Keep"]);
+      const pdas = await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(1000));
+      const lockedBalance = await provider.connection.getTokenAccountBalance(voter2TokenAccount);
+
+      const veto = (authority: Keypair) =>
+        program.methods
+          .vetoProposal()
+          .accounts({
+            authority: authority.publicKey,
+            governance: governancePDA,
+            proposal: proposal,
+          })
+          .signers([authority])
+          .rpc();
+
+      try {
+        await veto(voter1);
+        expect.fail("Only the governance authority should veto");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("Unauthorized");
+      }
+
+      await veto(tokenCreator);
+
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.status.rejected).to.exist;
+      expect(proposalAccount.vetoedBy.toString()).to.equal(tokenCreator.publicKey.toString());
+
+      await program.methods
+        .refundLosingEscrow()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          governance: governancePDA,
+          proposal: proposal,
+          choiceEscrow: pdas.choiceEscrow,
+          vaultAuthority: pdas.vaultAuthority,
+          escrowVault: pdas.choiceEscrowVault,
+          voterTokenAccount: voter2TokenAccount,
+          tokenMint: tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const refundedBalance = await provider.connection.getTokenAccountBalance(voter2TokenAccount);
+      expect(parseInt(refundedBalance.value.amount)).to.equal(
+        parseInt(lockedBalance.value.amount) + 1000
+      );
+    });
+  });
+
+  describe("Registry Authority Transfer", () => {
+    it("Should hand over registry authority only to the pending authority", async () => {
+      const community = await createCommunity("Handover Token");
+      const voterTokenAccount = await fundWallet(community, voter2.publicKey, 1000);
+
+      // Created before the handover, so it records the old authority as token creator
+      const proposal = await createProposal(community, voter2, "Pre-Handover Proposal", ["Yes", "No"]);
+      const pdas = await lockTokens(community, proposal, voter2, voterTokenAccount, 0, new BN(1000));
+
+      await program.methods
+        .proposeRegistryTransfer(voter1.publicKey)
+        .accounts({
+          authority: tokenCreator.publicKey,
+          tokenRegistry: community.registry,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      let registryAccount = await program.account.tokenRegistry.fetch(community.registry);
+      expect(registryAccount.pendingAuthority.toString()).to.equal(voter1.publicKey.toString());
+
+      const accept = (newAuthority: Keypair, governance: PublicKey | null = community.governance) =>
+        program.methods
+          .acceptRegistryTransfer()
+          .accounts({
+            newAuthority: newAuthority.publicKey,
+            tokenRegistry: community.registry,
+            governance: governance,
+          })
+          .signers([newAuthority])
+          .rpc();
+
+      try {
+        await accept(voter2);
+        expect.fail("Only the pending authority should accept");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("NotPendingAuthority");
+      }
+
+      try {
+        await accept(voter1, null);
+        expect.fail("Should require the governance account once governance is enabled");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("GovernanceAccountRequired");
+      }
+
+      await accept(voter1);
+
+      registryAccount = await program.account.tokenRegistry.fetch(community.registry);
+      expect(registryAccount.authority.toString()).to.equal(voter1.publicKey.toString());
+      expect(registryAccount.pendingAuthority).to.be.null;
+
+      const governanceAccount = await program.account.governance.fetch(community.governance);
+      expect(governanceAccount.authority.toString()).to.equal(voter1.publicKey.toString());
+      expect(governanceAccount.tokenRegistry.toString()).to.equal(community.registry.toString());
+
+      // Settlement of older proposals now also belongs to the new authority
+      await program.methods
+        .vetoProposal()
+        .accounts({
+          authority: voter1.publicKey,
+          governance: community.governance,
+          proposal: proposal,
+        })
+        .signers([voter1])
+        .rpc();
+
+      const refund = (executor: Keypair) =>
+        program.methods
+          .refundLosingEscrow()
+          .accounts({
+            executor: executor.publicKey,
+            governance: community.governance,
+            proposal: proposal,
+            choiceEscrow: pdas.choiceEscrow,
+            vaultAuthority: pdas.vaultAuthority,
+            escrowVault: pdas.choiceEscrowVault,
+            voterTokenAccount: voterTokenAccount,
+            tokenMint: community.mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([executor])
+          .rpc();
+
+      try {
+        await refund(tokenCreator);
+        expect.fail("The previous authority should no longer settle escrows");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("Unauthorized");
+      }
+
+      await refund(voter1);
+      const escrowAccount = await program.account.choiceEscrow.fetch(pdas.choiceEscrow);
+      expect(escrowAccount.distributed).to.be.true;
+    });
+  });
+
+  describe("Active Proposal Limit", () => {
+    it("Should reject proposals beyond the active cap until one is closed", async () => {
+      const community = await createCommunity("Busy Token", {
+        maxActiveProposals: 2,
+        holders: [[voter1.publicKey, 1000]],
+      });
+
+      const first = await createProposal(community, voter1, "Active 1", ["Yes", "No"]);
+      await createProposal(community, voter1, "Active 2", ["Yes", "No"]);
+
+      let governanceAccount = await program.account.governance.fetch(community.governance);
+      expect(governanceAccount.activeProposalCount.toNumber()).to.equal(2);
+
+      try {
+        await createProposal(community, voter1, "Active 3", ["Yes", "No"]);
+        expect.fail("Should have rejected a proposal beyond the active cap");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("TooManyActiveProposals");
+      }
+
+      // Closing one proposal frees a slot
+      await program.methods
+        .vetoProposal()
+        .accounts({
+          authority: tokenCreator.publicKey,
+          governance: community.governance,
+          proposal: first,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      governanceAccount = await program.account.governance.fetch(community.governance);
+      expect(governanceAccount.activeProposalCount.toNumber()).to.equal(1);
+
+      await createProposal(community, voter1, "Active 3", ["Yes", "No"]);
+    });
+  });
+
+  describe("Proposal Categories", () => {
+    it("Should store each proposal's category for filtering", async () => {
+      const governanceAccount = await program.account.governance.fetch(governancePDA);
+      const treasuryId = governanceAccount.proposalCount;
+      const treasury = await createProposal(mainCommunity(), voter1, "Treasury Spend", ["Yes", "No"], { category: 1 });
+      const parameter = await createProposal(mainCommunity(), voter1, "Parameter Change", ["Yes", "No"], { category: 2 });
+
+      expect((await program.account.multiChoiceProposal.fetch(treasury)).category).to.equal(1);
+      expect((await program.account.multiChoiceProposal.fetch(parameter)).category).to.equal(2);
+
+      const results = await program.methods
+        .getProposalResults(treasuryId)
+        .accounts({
+          governance: governancePDA,
+          proposal: treasury,
+        })
+        .view();
+      expect(results.category).to.equal(1);
+    });
+  });
+
+  describe("Stored Bumps", () => {
+    it("Should store the canonical bumps of governance and escrow accounts", async () => {
+      const [, governanceBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("governance"), tokenMint.toBuffer()],
+        program.programId
+      );
+      const governanceAccount = await program.account.governance.fetch(governancePDA);
+      expect(governanceAccount.bump).to.equal(governanceBump);
+
+      const proposal = await createProposal(mainCommunity(), voter1, "Bump Proposal", ["Yes", "No"]);
+      const pdas = await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 1, new BN(100));
+      const [, escrowBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("choice_escrow"), proposal.toBuffer(), Buffer.from([1]), voter2.publicKey.toBuffer()],
+        program.programId
+      );
+      const escrowAccount = await program.account.choiceEscrow.fetch(pdas.choiceEscrow);
+      expect(escrowAccount.bump).to.equal(escrowBump);
+    });
+  });
+
+  describe("Vote Records", () => {
+    it("Should record when a vote was cast and the power it credited", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Recorded Proposal", ["Yes", "No"]);
+      const before = Math.floor(Date.now() / 1000);
+      const pdas = await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(500));
+      await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(250));
+
+      const escrowAccount = await program.account.choiceEscrow.fetch(pdas.choiceEscrow);
+      expect(escrowAccount.creditedPower.toNumber()).to.equal(750);
+      expect(escrowAccount.lockedAmount.toNumber()).to.equal(750);
+      // Allow for clock drift between the validator and the test runner
+      expect(escrowAccount.votedAt.toNumber()).to.be.closeTo(before, 60);
+    });
+  });
+
+  describe("Token Info Updates", () => {
+    it("Should rename a registered token and validate lengths", async () => {
+      const community = await createCommunity("Old Name");
+
+      const update = (name: string | null, symbol: string | null) =>
+        program.methods
+          .updateTokenInfo(name, symbol)
+          .accounts({
+            authority: tokenCreator.publicKey,
+            tokenRegistry: community.registry,
+          })
+          .signers([tokenCreator])
+          .rpc();
+
+      await update("Rebranded Token", "RBT");
+      let registryAccount = await program.account.tokenRegistry.fetch(community.registry);
+      expect(registryAccount.tokenName).to.equal("Rebranded Token");
+      expect(registryAccount.tokenSymbol).to.equal("RBT");
+      expect(registryAccount.tokenMint.toString()).to.equal(community.mint.toString());
+
+      // Omitted fields are left unchanged
+      await update(null, "RBT2");
+      registryAccount = await program.account.tokenRegistry.fetch(community.registry);
+      expect(registryAccount.tokenName).to.equal("Rebranded Token");
+      expect(registryAccount.tokenSymbol).to.equal("RBT2");
+
+      for (const [code, name, symbol] of [
+        ["NameTooLong", "n".repeat(33), null],
+        ["SymbolTooLong", null, "s".repeat(9)],
+      ]) {
+        try {
+          await update(name, symbol);
+          expect.fail(`Should have failed with ${code}`);
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal(code);
+        }
+      }
+    });
+  });
+
+  describe("Batch Refunds", () => {
+    it("Should refund three losing escrows in a single call", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Batch Refund Proposal", ["Yes", "No"]);
+      const loserAmount = new BN(10 * Math.pow(10, 6));
+
+      // The creator's larger vote makes "Yes" win; the three voters lose on "No"
+      const winner = await lockTokens(
+        mainCommunity(), proposal, tokenCreator, creatorTokenAccount, 0, new BN(100 * Math.pow(10, 6))
+      );
+      const voters: [Keypair, PublicKey][] = [
+        [voter1, voter1TokenAccount],
+        [voter2, voter2TokenAccount],
+        [voter3, voter3TokenAccount],
+      ];
+      const losers = [];
+      for (const [voter, tokenAccount] of voters) {
+        losers.push(await lockTokens(mainCommunity(), proposal, voter, tokenAccount, 1, loserAmount));
+      }
+
+      console.log("Waiting for the batch refund proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      await program.methods
+        .executeProposal()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          tokenRegistry: tokenRegistryPDA,
+          governance: governancePDA,
+          proposal: proposal,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const balances = async () =>
+        Promise.all(
+          voters.map(async ([, tokenAccount]) =>
+            parseInt((await provider.connection.getTokenAccountBalance(tokenAccount)).value.amount)
+          )
+        );
+      const beforeBalances = await balances();
+
+      // The winning escrow is included to show it is skipped
+      const groups = [
+        { pdas: winner, tokenAccount: creatorTokenAccount },
+        ...losers.map((pdas, i) => ({ pdas, tokenAccount: voters[i][1] })),
+      ];
+      const refundBatch = (vaults: PublicKey[]) =>
+        program.methods
+          .refundLosingEscrowsBatch()
+          .accounts({
+            executor: tokenCreator.publicKey,
+            governance: governancePDA,
+            proposal: proposal,
+            tokenMint: tokenMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .remainingAccounts(
+            groups.flatMap(({ pdas, tokenAccount }, i) => [
+              { pubkey: pdas.choiceEscrow, isSigner: false, isWritable: true },
+              { pubkey: vaults[i], isSigner: false, isWritable: true },
+              { pubkey: pdas.vaultAuthority, isSigner: false, isWritable: false },
+              { pubkey: tokenAccount, isSigner: false, isWritable: true },
+            ])
+          )
+          .signers([tokenCreator])
+          .rpc();
+      const realVaults = groups.map(({ pdas }) => pdas.choiceEscrowVault);
+
+      // A token account owned by the vault authority is not the escrow's vault
+      const foreignVault = await createAccount(
+        provider.connection,
+        tokenCreator,
+        tokenMint,
+        losers[0].vaultAuthority,
+        Keypair.generate()
+      );
+      await mintTo(provider.connection, tokenCreator, tokenMint, foreignVault, tokenCreator, loserAmount.toNumber());
+      try {
+        await refundBatch([realVaults[0], foreignVault, ...realVaults.slice(2)]);
+        expect.fail("Should have rejected a vault that is not the escrow's own");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidBatchAccounts");
+      }
+
+      await refundBatch(realVaults);
+
+      const afterBalances = await balances();
+      afterBalances.forEach((after, i) => {
+        expect(after - beforeBalances[i]).to.equal(loserAmount.toNumber());
+      });
+
+      for (const pdas of losers) {
+        const escrowAccount = await program.account.choiceEscrow.fetch(pdas.choiceEscrow);
+        expect(escrowAccount.distributed).to.be.true;
+      }
+      const winningEscrow = await program.account.choiceEscrow.fetch(winner.choiceEscrow);
+      expect(winningEscrow.distributed).to.be.false;
+
+      const settledProposal = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(settledProposal.openEscrowCount.toNumber()).to.equal(1);
+    });
+  });
+
+  describe("Ended Voting", () => {
+    it("Should reject votes on an ended but unexecuted proposal", async () => {
+      const proposal = await createProposal(mainCommunity(), voter1, "Ended Proposal", ["Yes", "No"]);
+
+      console.log("Waiting for the ended proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.status.active).to.exist;
+
+      try {
+        await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(100));
+        expect.fail("Should have rejected a vote after the voting period");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("VotingEnded");
+      }
+    });
+  });
+
+  describe("Registry Index", () => {
+    it("Should list every registered token mint across index pages", async () => {
+      const listMints = async (): Promise<string[]> => {
+        const registryIndex = await program.account.registryIndex.fetch(registryIndexPDA);
+        const mints: string[] = [];
+        for (let page = 0; page <= registryIndex.currentPage; page++) {
+          const pageAccount = await program.account.registryIndexPage.fetchNullable(registryIndexPage(page));
+          mints.push(...(pageAccount?.mints ?? []).map((mint) => mint.toString()));
+        }
+        expect(mints.length).to.equal(registryIndex.totalMints.toNumber());
+        return mints;
+      };
+
+      const before = await listMints();
+
+      const communities = [];
+      for (const name of ["Indexed One", "Indexed Two", "Indexed Three"]) {
+        communities.push(await createCommunity(name));
+      }
+
+      const listed = await listMints();
+      expect(listed.length).to.equal(before.length + 3);
+      expect(listed).to.include(tokenMint.toString());
+      for (const community of communities) {
+        expect(listed).to.include(community.mint.toString());
+      }
+    });
+  });
+
+  describe("Proposal Phase", () => {
+    it("Should report the phase and remaining time from chain time", async () => {
+      const getPhase = async (proposal: PublicKey) => {
+        const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+        return program.methods
+          .getProposalPhase(proposalAccount.id)
+          .accounts({
+            governance: governancePDA,
+            proposal: proposal,
+          })
+          .view();
+      };
+
+      const slot = await provider.connection.getSlot();
+      const chainTime = await provider.connection.getBlockTime(slot);
+      const pendingProposal = await createProposal(mainCommunity(), voter1, "Pending Phase", ["Yes", "No"], {
+        startAt: new BN(chainTime + 30),
+      });
+      const pending = await getPhase(pendingProposal);
+      expect(pending.phase.pending).to.exist;
+      expect(pending.secondsUntilStart.toNumber()).to.be.greaterThan(0);
+      expect(pending.secondsUntilEnd.toNumber()).to.be.greaterThan(pending.secondsUntilStart.toNumber());
+
+      const proposal = await createProposal(mainCommunity(), voter1, "Voting Phase", ["Yes", "No"]);
+      await lockTokens(mainCommunity(), proposal, voter2, voter2TokenAccount, 0, new BN(100 * Math.pow(10, 6)));
+      const voting = await getPhase(proposal);
+      expect(voting.phase.voting).to.exist;
+      expect(voting.secondsUntilStart.toNumber()).to.equal(0);
+      expect(voting.secondsUntilEnd.toNumber()).to.be.greaterThan(0);
+      expect(voting.secondsUntilEnd.toNumber()).to.be.at.most(VOTING_PERIOD.toNumber());
+
+      console.log("Waiting for the phase proposal voting period to end...");
+      await sleep(65 * 1000);
+
+      const ended = await getPhase(proposal);
+      expect(ended.phase.ended).to.exist;
+      expect(ended.secondsUntilEnd.toNumber()).to.equal(0);
+
+      await program.methods
+        .executeProposal()
+        .accounts({
+          executor: tokenCreator.publicKey,
+          tokenRegistry: tokenRegistryPDA,
+          governance: governancePDA,
+          proposal: proposal,
+        })
+        .signers([tokenCreator])
+        .rpc();
+
+      const executed = await getPhase(proposal);
+      expect(executed.phase.executed).to.exist;
+    });
+  });
+
+  describe("Minimum Vote Amount", () => {
+    it("Should reject votes below the governance minimum", async () => {
+      const minVoteAmount = new BN(5 * Math.pow(10, 6));
+      const community = await createCommunity("Dust Guard Token", {
+        minVoteAmount,
+        holders: [[voter1.publicKey, 1000]],
+      });
+      const voterTokenAccount = await fundWallet(community, voter2.publicKey, 100);
+      const proposal = await createProposal(community, voter1, "Minimum Vote", ["Yes", "No"]);
+
+      try {
+        await lockTokens(community, proposal, voter2, voterTokenAccount, 0, minVoteAmount.subn(1));
+        expect.fail("Should have rejected a vote below the minimum");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("VoteAmountTooSmall");
+      }
+
+      await lockTokens(community, proposal, voter2, voterTokenAccount, 0, minVoteAmount);
+      const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+      expect(proposalAccount.choiceVoteCounts[0].toString()).to.equal(minVoteAmount.toString());
+    });
+
+    it("Should not let a reduction leave less than the governance minimum", async () => {
+      const minVoteAmount = new BN(5 * Math.pow(10, 6));
+      const community = await createCommunity("Dust Reduction Token", {
+        minVoteAmount,
+        holders: [[voter1.publicKey, 1000]],
+      });
+      const voterTokenAccount = await fundWallet(community, voter2.publicKey, 100);
+      const proposal = await createProposal(community, voter1, "Minimum Remaining", ["Yes", "No"]);
+      const pdas = await lockTokens(community, proposal, voter2, voterTokenAccount, 0, minVoteAmount.muln(2));
+
+      const reduce = (amount: BN) =>
+        program.methods
+          .reduceVote(amount)
+          .accounts({
+            voter: voter2.publicKey,
+            governance: community.governance,
+            proposal: proposal,
+            choiceEscrow: pdas.choiceEscrow,
+            vaultAuthority: pdas.vaultAuthority,
+            escrowVault: pdas.choiceEscrowVault,
+            voterTokenAccount: voterTokenAccount,
+            tokenMint: community.mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([voter2])
+          .rpc();
+
+      try {
+        await reduce(minVoteAmount.muln(2).subn(1));
+        expect.fail("Should have rejected a reduction leaving a dust vote");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("VoteAmountTooSmall");
+      }
+
+      // Reducing down to exactly the minimum, then withdrawing fully, are both allowed
+      await reduce(minVoteAmount);
+      await reduce(minVoteAmount);
+      const escrowAccount = await program.account.choiceEscrow.fetch(pdas.choiceEscrow);
+      expect(escrowAccount.lockedAmount.toNumber()).to.equal(0);
+    });
+  });
+
+  describe("Voting Period Ceiling", () => {
+    it("Should clamp proposal duration overrides to the governance maximum", async () => {
+      const community = await createCommunity("Ceiling Token", {
+        maxVotingPeriod: 3600,
+        holders: [[voter1.publicKey, 1000]],
+      });
+
+      const duration = async (proposal: PublicKey) => {
+        const proposalAccount = await program.account.multiChoiceProposal.fetch(proposal);
+        return proposalAccount.endsAt.sub(proposalAccount.startAt).toNumber();
+      };
+
+      const withinRange = await createProposal(community, voter1, "Short Override", ["Yes", "No"], {
+        votingDuration: new BN(120),
+      });
+      expect(await duration(withinRange)).to.equal(120);
+
+      const beyondMax = await createProposal(community, voter1, "Long Override", ["Yes", "No"], {
+        votingDuration: new BN(86400),
+      });
+      expect(await duration(beyondMax)).to.equal(3600);
+
+      try {
+        await createProposal(community, voter1, "Zero Override", ["Yes", "No"], {
+          votingDuration: new BN(0),
+        });
+        expect.fail("Should have rejected a zero duration override");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("VotingDurationTooShort");
+      }
+    });
+  });
+
+  describe("Token Holder Check", () => {
+    it("Should report holders and non-holders without erroring", async () => {
+      const checkHolder = (tokenAccount: PublicKey) =>
+        program.methods
+          .checkTokenHolder()
+          .accounts({
+            tokenRegistry: tokenRegistryPDA,
+            tokenAccount: tokenAccount,
+          })
+          .view();
+
+      expect(await checkHolder(voter1TokenAccount)).to.be.true;
+
+      const emptyTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          tokenCreator,
+          tokenMint,
+          Keypair.generate().publicKey
+        )
+      ).address;
+      expect(await checkHolder(emptyTokenAccount)).to.be.false;
+    });
   });
 });
\ No newline at end of file